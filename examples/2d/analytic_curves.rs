@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_analytic_curves".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // The left SVG has its curves flattened, the right one keeps them and renders them in the
+    // fragment shader, with far fewer vertices. Zoom in to compare.
+    let flattened = asset_server.load("neutron_star.svg");
+    let analytic =
+        asset_server.load_with_settings("neutron_star.svg", |settings: &mut SvgLoaderSettings| {
+            settings.curves = SvgCurves::Analytic;
+        });
+    commands.spawn(Camera2d::default());
+    commands.spawn((
+        Svg2d(flattened),
+        Origin::Center,
+        Transform::from_xyz(-150.0, 0.0, 0.0),
+    ));
+    commands.spawn((
+        Svg2d(analytic),
+        Origin::Center,
+        Transform::from_xyz(150.0, 0.0, 0.0),
+    ));
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_anti_aliasing".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // The left SVG is aliased without MSAA, the right one is smoothed by its fringe
+    let aliased = asset_server.load("neutron_star.svg");
+    let fringed =
+        asset_server.load_with_settings("neutron_star.svg", |settings: &mut SvgLoaderSettings| {
+            settings.anti_aliasing = SvgAntiAliasing::Fringe;
+        });
+    commands.spawn((Camera2d::default(), Msaa::Off));
+    commands.spawn((
+        Svg2d(aliased),
+        Origin::Center,
+        Transform::from_xyz(-150.0, 0.0, 0.0),
+    ));
+    commands.spawn((
+        Svg2d(fringed),
+        Origin::Center,
+        Transform::from_xyz(150.0, 0.0, 0.0),
+    ));
+}
@@ -0,0 +1,67 @@
+use bevy::{color::palettes::css, math::Affine2, prelude::*};
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_builder".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>, mut svgs: ResMut<Assets<Svg>>) {
+    // A bar chart, built from shapes without writing SVG text
+    let values = [3.0, 7.0, 4.0, 9.0, 6.0];
+    let mut builder = SvgBuilder::new(Vec2::new(400.0, 300.0));
+    builder.name("chart").rect(
+        Rect::new(0.0, 0.0, 400.0, 300.0),
+        ShapeStyle::fill(css::WHITE).with_stroke(css::DARK_GRAY, 2.0),
+    );
+    for (i, value) in values.iter().enumerate() {
+        let x = 50.0 + i as f32 * 65.0;
+        builder
+            .rect(
+                Rect::new(x, 250.0 - value * 20.0, x + 45.0, 250.0),
+                css::STEEL_BLUE,
+            )
+            .circle(
+                Vec2::new(x + 22.5, 250.0 - value * 20.0),
+                5.0,
+                ShapeStyle::fill(css::ORANGE).with_stroke(css::BLACK, 1.0),
+            )
+            .text(
+                format!("{value}"),
+                Vec2::new(x + 16.0, 280.0),
+                20.0,
+                css::BLACK,
+            );
+    }
+    builder
+        .path(
+            "M 40 250 H 380 M 40 250 V 30",
+            ShapeStyle::stroke(css::BLACK, 2.0),
+        )
+        .transform(Affine2::from_translation(Vec2::new(40.0, 30.0)))
+        .polygon(
+            [
+                Vec2::new(-6.0, 0.0),
+                Vec2::new(0.0, -10.0),
+                Vec2::new(6.0, 0.0),
+            ],
+            css::BLACK,
+        );
+    let svg = builder.build_with_mesh(&mut meshes).expect("valid chart");
+
+    commands.spawn(Camera2d::default());
+    commands.spawn((Svg2d(svgs.add(svg)), Origin::Center));
+}
@@ -0,0 +1,62 @@
+use bevy::{color::palettes::css, prelude::*};
+use bevy_svg::prelude::*;
+use std::sync::Arc;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_editing".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_hand)
+        .run();
+}
+
+#[derive(Resource)]
+struct Clock(Handle<Svg>);
+
+fn setup(mut commands: Commands, mut svgs: ResMut<Assets<Svg>>) {
+    let mut builder = SvgBuilder::new(Vec2::splat(300.0));
+    builder
+        .circle(
+            Vec2::splat(150.0),
+            140.0,
+            ShapeStyle::fill(css::WHITE).with_stroke(css::BLACK, 8.0),
+        )
+        .rect(Rect::new(146.0, 30.0, 154.0, 150.0), css::CRIMSON);
+    // The mesh is tessellated when the SVG is added, and again whenever its paths are edited
+    let clock = svgs.add(builder.build().expect("valid clock"));
+    commands.spawn(Camera2d::default());
+    commands.spawn((Svg2d(clock.clone()), Origin::Center));
+    commands.insert_resource(Clock(clock));
+}
+
+fn move_hand(time: Res<Time>, clock: Res<Clock>, mut svgs: ResMut<Assets<Svg>>) {
+    let Some(hand) = svgs
+        .get_mut(&clock.0)
+        .and_then(|svg| Arc::make_mut(&mut svg.paths).last_mut())
+    else {
+        return;
+    };
+    let angle = time.elapsed_secs();
+    let center = 150.0;
+    let (sin, cos) = angle.sin_cos();
+    // Rotates the hand around the center of the clock
+    hand.abs_transform = usvg::Transform::from_row(
+        cos,
+        sin,
+        -sin,
+        cos,
+        center - cos * center + sin * center,
+        center - sin * center - cos * center,
+    );
+}
@@ -0,0 +1,58 @@
+use bevy::{asset::io::embedded::EmbeddedAssetRegistry, prelude::*};
+use bevy_svg::prelude::*;
+use std::path::Path;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+const STAR: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200">
+  <polygon points="100,10 129,70 195,78 147,124 159,190 100,160 41,190 53,124 5,78 71,70"
+    fill="#f5c242" stroke="#8a5a00" stroke-width="4"/>
+</svg>"##;
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            title: "2d_embedded".to_string(),
+            resolution: (600., 600.).into(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }))
+    .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+    .add_systems(Startup, setup);
+
+    // In a crate, `embedded_asset!(app, "box.svg")` does the same for a file next to the source
+    app.world_mut()
+        .resource_mut::<EmbeddedAssetRegistry>()
+        .insert_asset(
+            "".into(),
+            Path::new("embedded_example/box.svg"),
+            include_bytes!("../../assets/box.svg"),
+        );
+
+    app.run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut svgs: ResMut<Assets<Svg>>,
+) {
+    // The left SVG is shipped inside the binary, the right one is parsed from a string
+    let embedded = asset_server.load("embedded://embedded_example/box.svg");
+    let parsed = Svg::from_str_with_mesh(STAR, &mut meshes).expect("valid SVG");
+    commands.spawn(Camera2d::default());
+    commands.spawn((
+        Svg2d(embedded),
+        Origin::Center,
+        Transform::from_xyz(-150.0, 0.0, 0.0),
+    ));
+    commands.spawn((
+        Svg2d(svgs.add(parsed)),
+        Origin::Center,
+        Transform::from_xyz(150.0, 0.0, 0.0),
+    ));
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_fit".to_string(),
+                resolution: (800., 400.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let svg = asset_server.load("neutron_star.svg");
+    commands.spawn(Camera2d::default());
+    // The same SVG fitted into the same size in all modes, the box shows the size
+    let size = Vec2::new(200.0, 100.0);
+    for (x, mode) in [
+        (-300.0, SvgFitMode::AspectRatio),
+        (-100.0, SvgFitMode::Fit),
+        (100.0, SvgFitMode::Fill),
+        (300.0, SvgFitMode::Stretch),
+    ] {
+        commands.spawn((
+            Sprite::from_color(Color::srgb(0.2, 0.2, 0.3), size),
+            Transform::from_xyz(x, 0.0, -1.0),
+        ));
+        commands.spawn((
+            Svg2d(svg.clone()),
+            SvgFit::with_mode(size, mode),
+            Origin::Center,
+            Transform::from_xyz(x, 0.0, 0.0),
+        ));
+    }
+}
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_msdf".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, spawn_msdf)
+        .run();
+}
+
+#[derive(Resource)]
+struct Icons(Handle<Svg>);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let svg = asset_server.load("icons.svg");
+    commands.spawn(Camera2d::default());
+    // The left SVG is a mesh, the right one is drawn from its distance field once it is loaded
+    commands.spawn((
+        Svg2d(svg.clone()),
+        Origin::Center,
+        Transform::from_xyz(-150.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
+    ));
+    commands.insert_resource(Icons(svg));
+}
+
+fn spawn_msdf(
+    mut commands: Commands,
+    mut svg_events: EventReader<AssetEvent<Svg>>,
+    icons: Res<Icons>,
+    svgs: Res<Assets<Svg>>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<SvgMsdfMaterial>>,
+) {
+    for event in svg_events.read() {
+        if !event.is_loaded_with_dependencies(&icons.0) {
+            continue;
+        }
+        let svg = svgs.get(&icons.0).unwrap();
+        // A field with a pixel per unit of the SVG stays sharp when zoomed in, only details
+        // smaller than a pixel get rounded
+        let (size, distance_range) = (svg.size.as_uvec2(), 4.0);
+        let texture = images.add(svg.generate_msdf(size.x, size.y, distance_range));
+        commands.spawn((
+            Mesh2d(meshes.add(Rectangle::from_size(svg.size))),
+            MeshMaterial2d(materials.add(SvgMsdfMaterial {
+                color: LinearRgba::rgb(0.9, 0.3, 0.3),
+                distance_range,
+                texture,
+            })),
+            Transform::from_xyz(150.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
+        ));
+    }
+}
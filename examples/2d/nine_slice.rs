@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_nine_slice".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, resize)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let svg = asset_server.load("panel.svg");
+    commands.spawn(Camera2d::default());
+    // The rounded corners keep their size, while the panel is stretched
+    commands.spawn((
+        Svg2d(svg.clone()),
+        SvgSlices::uniform(20.0, Vec2::new(400.0, 150.0)),
+        Origin::Center,
+        Transform::from_xyz(0.0, 150.0, 0.0),
+    ));
+    // A three-slice is only stretched horizontally
+    commands.spawn((
+        Svg2d(svg),
+        SvgSlices::horizontal(20.0, 20.0, 400.0, 64.0),
+        Origin::Center,
+        Transform::from_xyz(0.0, -100.0, 0.0),
+    ));
+}
+
+fn resize(time: Res<Time>, mut query: Query<&mut SvgSlices>) {
+    for mut slices in &mut query {
+        slices.size.x = 300.0 + 150.0 * time.elapsed_secs().sin();
+    }
+}
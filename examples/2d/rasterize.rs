@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_rasterize".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // The left SVG is a mesh, the right one a sprite with the SVG rasterized into its image
+    let svg = asset_server.load_with_settings("box.svg", |settings: &mut SvgLoaderSettings| {
+        settings.image_size = Some(UVec2::new(256, 256));
+    });
+    let image = asset_server.load("box.svg#image");
+    commands.spawn(Camera2d::default());
+    commands.spawn((
+        Svg2d(svg),
+        Origin::Center,
+        Transform::from_xyz(-150.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
+    ));
+    commands.spawn((
+        Sprite {
+            image,
+            // The same size as the SVG
+            custom_size: Some(Vec2::splat(100.0)),
+            ..Default::default()
+        },
+        Transform::from_xyz(150.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
+    ));
+}
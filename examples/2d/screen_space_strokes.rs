@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_screen_space_strokes".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        // Keeps the width of all strokes constant while zooming
+        .insert_resource(StrokeScaling::ScreenSpace)
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_stroke_scaling)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let svg = asset_server.load("neutron_star.svg");
+    commands.spawn(Camera2d::default());
+    commands.spawn((
+        Svg2d(svg),
+        Origin::Center,
+        Transform::from_scale(Vec3::new(10.0, 10.0, 1.0)),
+    ));
+}
+
+/// Switches between screen space and scaled strokes with the `S` key.
+fn toggle_stroke_scaling(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut stroke_scaling: ResMut<StrokeScaling>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyS) {
+        *stroke_scaling = match *stroke_scaling {
+            StrokeScaling::Scaled => StrokeScaling::ScreenSpace,
+            StrokeScaling::ScreenSpace => StrokeScaling::Scaled,
+        };
+    }
+}
@@ -0,0 +1,54 @@
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_sprite".to_string(),
+                resolution: (800., 400.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, flip)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let svg = asset_server.load("neutron_star.svg");
+    commands.spawn(Camera2d::default());
+    // Spawned with the same options as a `Sprite`
+    commands.spawn((
+        SvgSprite::from_svg(svg.clone()),
+        Transform::from_xyz(-250.0, 0.0, 0.0),
+    ));
+    commands.spawn((
+        SvgSprite::sized(svg.clone(), Vec2::new(200.0, 100.0)),
+        Transform::from_xyz(0.0, 0.0, 0.0),
+    ));
+    commands.spawn((
+        SvgSprite {
+            svg,
+            custom_size: Some(Vec2::new(150.0, 150.0)),
+            flip_y: true,
+            anchor: Anchor::BottomLeft,
+            ..Default::default()
+        },
+        Transform::from_xyz(175.0, -75.0, 0.0),
+    ));
+}
+
+fn flip(time: Res<Time>, mut query: Query<&mut SvgSprite>) {
+    let flip_x = time.elapsed_secs() as u32 % 2 == 1;
+    for mut sprite in &mut query {
+        if sprite.flip_x != flip_x {
+            sprite.flip_x = flip_x;
+        }
+    }
+}
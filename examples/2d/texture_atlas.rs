@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_texture_atlas".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, build_atlas)
+        .run();
+}
+
+#[derive(Resource)]
+struct SvgHandles {
+    icons: Handle<Svg>,
+    star: Handle<Svg>,
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+    commands.insert_resource(SvgHandles {
+        icons: asset_server.load("icons.svg"),
+        star: asset_server.load("neutron_star.svg"),
+    });
+}
+
+/// Rasterizes the SVGs into an atlas once they are loaded, and spawns a grid of sprites from it.
+fn build_atlas(
+    mut commands: Commands,
+    mut built: Local<bool>,
+    handles: Res<SvgHandles>,
+    svgs: Res<Assets<Svg>>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    if *built {
+        return;
+    }
+    let (Some(icons), Some(star)) = (svgs.get(&handles.icons), svgs.get(&handles.star)) else {
+        return;
+    };
+    *built = true;
+
+    let mut builder = SvgAtlasBuilder::default();
+    builder
+        .add_svg("neutron_star.svg", star, UVec2::splat(64))
+        .add_element(icons, "heart", UVec2::splat(64))
+        .add_element(icons, "star", UVec2::splat(64))
+        .add_element(icons, "drop", UVec2::splat(64));
+    let atlas = builder.build().expect("the SVGs fit into the atlas");
+    let image = images.add(atlas.image.clone());
+    let layout = layouts.add(atlas.layout.clone());
+
+    // All sprites are drawn from the same image, so they are batched
+    let names = ["heart", "star", "drop"];
+    for y in 0..8 {
+        for x in 0..8 {
+            let texture_atlas = if (x + y) % 4 == 0 {
+                atlas.path_texture_atlas(layout.clone(), "neutron_star.svg")
+            } else {
+                atlas.element_texture_atlas(layout.clone(), names[(x + y) % 4 - 1])
+            };
+            commands.spawn((
+                Sprite::from_atlas_image(image.clone(), texture_atlas.unwrap()),
+                Transform::from_xyz(x as f32 * 70.0 - 245.0, y as f32 * 70.0 - 245.0, 0.0),
+            ));
+        }
+    }
+}
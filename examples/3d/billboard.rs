@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "3d_billboard".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, orbit_camera)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let svg = asset_server.load("neutron_star.svg");
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 3.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(10.0, 10.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    commands.spawn((PointLight::default(), Transform::from_xyz(0.0, 4.0, 0.0)));
+
+    commands.spawn((
+        Svg3d(svg.clone()),
+        Svg3dBillboard::spherical(),
+        Origin::Center,
+        Transform::from_xyz(-3.0, 1.0, 0.0).with_scale(Vec3::new(0.03, 0.03, 1.0)),
+    ));
+    commands.spawn((
+        Svg3d(svg.clone()),
+        Svg3dBillboard::cylindrical(),
+        Origin::BottomLeft,
+        Transform::from_xyz(0.0, 0.0, 0.0).with_scale(Vec3::new(0.03, 0.03, 1.0)),
+    ));
+    // Stays about 100 pixels wide, however far away it is
+    commands.spawn((
+        Svg3d(svg),
+        Svg3dBillboard::spherical().with_fixed_size(),
+        Origin::Center,
+        Transform::from_xyz(3.0, 1.0, 0.0).with_scale(Vec3::new(2.4, 2.4, 1.0)),
+    ));
+}
+
+fn orbit_camera(time: Res<Time>, mut query: Query<&mut Transform, With<Camera3d>>) {
+    for mut transform in &mut query {
+        let angle = time.elapsed_secs() * 0.3;
+        let distance = 8.0 + 4.0 * (time.elapsed_secs() * 0.5).sin();
+        *transform = Transform::from_xyz(distance * angle.sin(), 3.0, distance * angle.cos())
+            .looking_at(Vec3::ZERO, Vec3::Y);
+    }
+}
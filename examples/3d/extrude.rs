@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "3d_extrude".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (spawn_extruded, rotate))
+        .run();
+}
+
+#[derive(Resource)]
+struct Logo(Handle<Svg>);
+
+#[derive(Component)]
+struct Extruded;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Logo(asset_server.load("neutron_star.svg")));
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 2.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight {
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+}
+
+fn spawn_extruded(
+    mut commands: Commands,
+    logo: Res<Logo>,
+    svgs: Res<Assets<Svg>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    query: Query<(), With<Extruded>>,
+) {
+    if !query.is_empty() {
+        return;
+    }
+    let Some(svg) = svgs.get(&logo.0) else {
+        return;
+    };
+
+    let mesh = svg.extrude(&ExtrudeOptions {
+        depth: 20.0,
+        bevel: 1.0,
+        side_normals: SideNormals::Smooth,
+        ..Default::default()
+    });
+    let center = svg.size * Vec2::new(0.5, -0.5);
+    commands
+        .spawn((
+            Extruded,
+            Transform::from_scale(Vec3::splat(0.01)),
+            Visibility::default(),
+        ))
+        .with_child((
+            Mesh3d(meshes.add(mesh)),
+            MeshMaterial3d(materials.add(StandardMaterial::default())),
+            Transform::from_translation(-center.extend(0.0)),
+        ));
+}
+
+fn rotate(time: Res<Time>, mut query: Query<&mut Transform, With<Extruded>>) {
+    for mut transform in &mut query {
+        transform.rotate_y(time.delta_secs() * 0.5);
+    }
+}
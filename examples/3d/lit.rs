@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "3d_lit".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // A `StandardMaterial` needs normals to light the SVG
+    let svg =
+        asset_server.load_with_settings("neutron_star.svg", |settings: &mut SvgLoaderSettings| {
+            settings.vertex_attributes = SvgVertexAttributes::Standard;
+        });
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 3.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        Transform::from_xyz(2.0, 4.0, 3.0),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(10.0, 10.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+        Transform::from_xyz(0.0, -1.5, 0.0),
+    ));
+    commands.spawn((
+        Svg3d(svg),
+        // A white material keeps the colors of the SVG
+        Svg3dStandardMaterial(materials.add(StandardMaterial::default())),
+        Origin::Center,
+        Transform::from_scale(Vec3::new(0.01, 0.01, 1.0)),
+    ));
+}
@@ -56,6 +56,8 @@ fn setup_legend(mut commands: Commands, asset_server: Res<AssetServer>) {
             commands.spawn(TextSpan::new(" - Toggle Frame Diagnostics"));
             commands.spawn((TextSpan::new("\nO"), TextFont::from_font(font_bold.clone())));
             commands.spawn(TextSpan::new(" - Cycle through Origins"));
+            commands.spawn((TextSpan::new("\nB"), TextFont::from_font(font_bold.clone())));
+            commands.spawn(TextSpan::new(" - Cycle through Origin bounds"));
             commands.spawn((TextSpan::new("\nV"), TextFont::from_font(font_bold.clone())));
             commands.spawn(TextSpan::new(" - Toggle visibility"));
         });
@@ -64,12 +66,12 @@ fn setup_legend(mut commands: Commands, asset_server: Res<AssetServer>) {
 #[derive(Component)]
 pub struct DontChange;
 
-/// This system toggles SVG visibility when 'V' is pressed and toggles through
-/// origin when 'O' is pressed.
+/// This system toggles SVG visibility when 'V' is pressed, toggles through
+/// origin when 'O' is pressed and through origin bounds when 'B' is pressed.
 fn keyboard_input_system(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut svg_query: Query<
-        (&mut Origin, &mut Visibility),
+        (&mut Origin, &mut OriginBounds, &mut Visibility),
         (Or<(With<Svg2d>, With<Svg3d>)>, Without<DontChange>),
     >,
     mut ui_query: Query<
@@ -83,14 +85,14 @@ fn keyboard_input_system(
     >,
 ) {
     if keyboard_input.just_pressed(KeyCode::KeyV) {
-        for (_, mut visible) in svg_query.iter_mut() {
+        for (_, _, mut visible) in svg_query.iter_mut() {
             *visible = match *visible {
                 Visibility::Hidden => Visibility::Inherited,
                 Visibility::Visible | Visibility::Inherited => Visibility::Hidden,
             };
         }
     } else if keyboard_input.just_pressed(KeyCode::KeyO) {
-        for (mut origin, _) in svg_query.iter_mut() {
+        for (mut origin, ..) in svg_query.iter_mut() {
             *origin = match origin.as_ref() {
                 Origin::BottomLeft => Origin::BottomRight,
                 Origin::BottomRight => Origin::TopRight,
@@ -100,6 +102,14 @@ fn keyboard_input_system(
                 Origin::Custom(coord) => Origin::Custom(*coord),
             }
         }
+    } else if keyboard_input.just_pressed(KeyCode::KeyB) {
+        for (_, mut bounds, _) in svg_query.iter_mut() {
+            *bounds = match bounds.as_ref() {
+                OriginBounds::Size => OriginBounds::ViewBox,
+                OriginBounds::ViewBox => OriginBounds::Content,
+                OriginBounds::Content => OriginBounds::Size,
+            }
+        }
     } else if keyboard_input.just_pressed(KeyCode::KeyF) {
         for mut visible in &mut ui_query {
             *visible = match *visible {
@@ -257,11 +267,11 @@ fn setup_origin_text(mut commands: Commands, asset_server: Res<AssetServer>) {
 
 fn origin_text_update_system(
     mut text_query: Query<&mut TextSpan, With<OriginText>>,
-    query: Query<&Origin>,
+    query: Query<(&Origin, &OriginBounds)>,
 ) {
     for mut text in &mut text_query {
-        if let Some(origin) = query.iter().next() {
-            *text.write_span() = format!("{origin:?}");
+        if let Some((origin, bounds)) = query.iter().next() {
+            *text.write_span() = format!("{origin:?} ({bounds:?})");
         }
     }
 }
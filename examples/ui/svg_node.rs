@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "ui_svg_node".to_string(),
+                resolution: (600., 600.).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins(bevy_svg::prelude::SvgPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, resize)
+        .run();
+}
+
+/// Marks the node that is resized over time.
+#[derive(Component)]
+struct Resized;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let svg = asset_server.load("neutron_star.svg");
+    commands.spawn(Camera2d::default());
+    commands
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::SpaceEvenly,
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            // Without a size of its own, the node takes the size of the SVG
+            parent.spawn(SvgNode(svg.clone()));
+            // The SVG is fitted into the node, following its `preserveAspectRatio`
+            parent.spawn((
+                SvgNode(svg.clone()),
+                Node {
+                    width: Val::Px(300.0),
+                    height: Val::Px(100.0),
+                    ..Default::default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.2, 0.3)),
+                Resized,
+            ));
+            // A nine-slice keeps the corners of the panel at their size
+            parent.spawn((
+                SvgNode(asset_server.load("panel.svg")),
+                SvgSlices::uniform(20.0, Vec2::ZERO),
+                Node {
+                    width: Val::Px(400.0),
+                    height: Val::Px(80.0),
+                    ..Default::default()
+                },
+            ));
+            // Clipped by its parent, like any other UI image
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(100.0),
+                        height: Val::Px(100.0),
+                        overflow: Overflow::clip(),
+                        ..Default::default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.2, 0.2)),
+                ))
+                .with_child((
+                    SvgNode(svg),
+                    Node {
+                        width: Val::Px(200.0),
+                        height: Val::Px(200.0),
+                        flex_shrink: 0.0,
+                        ..Default::default()
+                    },
+                ));
+        });
+}
+
+fn resize(time: Res<Time>, mut query: Query<&mut Node, With<Resized>>) {
+    for mut node in &mut query {
+        node.width = Val::Px(300.0 + 100.0 * time.elapsed_secs().sin());
+    }
+}
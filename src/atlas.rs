@@ -0,0 +1,229 @@
+//! Texture atlases of rasterized SVGs, to draw many SVGs as batched sprites.
+
+use bevy::{
+    asset::{AssetPath, Handle},
+    image::Image,
+    log::debug,
+    math::UVec2,
+    sprite::{TextureAtlas, TextureAtlasBuilder, TextureAtlasBuilderError, TextureAtlasLayout},
+    utils::HashMap,
+};
+use thiserror::Error;
+
+use crate::svg::Svg;
+
+/// Builder that rasterizes many [`Svg`]s, or elements of them, into a single texture atlas.
+///
+/// Hundreds of distinct icons are drawn as hundreds of meshes with as many draw calls. Once
+/// rasterized into an atlas they are drawn as `Sprite`s from the same image, which are batched.
+///
+/// ```ignore
+/// let mut builder = SvgAtlasBuilder::default();
+/// builder
+///     .add_svg("icons/home.svg", svgs.get(&home).unwrap(), UVec2::splat(64))
+///     .add_element(svgs.get(&sheet).unwrap(), "settings", UVec2::splat(64));
+/// let atlas = builder.build()?;
+/// let index = atlas.index_of_path("icons/home.svg").unwrap();
+/// ```
+#[derive(Debug)]
+#[must_use]
+pub struct SvgAtlasBuilder<'a> {
+    entries: Vec<(SvgAtlasKey, &'a Svg, UVec2)>,
+    initial_size: UVec2,
+    max_size: UVec2,
+    padding: UVec2,
+}
+
+/// How an entry of an [`SvgAtlasBuilder`] is looked up.
+#[derive(Debug)]
+enum SvgAtlasKey {
+    Path(AssetPath<'static>),
+    Element(String),
+}
+
+impl Default for SvgAtlasBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            initial_size: UVec2::splat(256),
+            max_size: UVec2::splat(2048),
+            padding: UVec2::splat(2),
+        }
+    }
+}
+
+impl<'a> SvgAtlasBuilder<'a> {
+    /// Sets the initial size of the atlas in pixels, which is doubled until all SVGs fit.
+    pub fn initial_size(&mut self, size: UVec2) -> &mut Self {
+        self.initial_size = size;
+        self
+    }
+
+    /// Sets the maximum size of the atlas in pixels.
+    pub fn max_size(&mut self, size: UVec2) -> &mut Self {
+        self.max_size = size;
+        self
+    }
+
+    /// Sets the padding in pixels between the SVGs, which keeps neighbouring SVGs from bleeding
+    /// into each other when the atlas is sampled with linear filtering. Defaults to 2 pixels.
+    pub fn padding(&mut self, padding: UVec2) -> &mut Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Adds the whole SVG at the given size in pixels, fitted following its
+    /// `preserveAspectRatio`, to be looked up by its asset path.
+    pub fn add_svg(
+        &mut self,
+        path: impl Into<AssetPath<'static>>,
+        svg: &'a Svg,
+        size: UVec2,
+    ) -> &mut Self {
+        self.entries
+            .push((SvgAtlasKey::Path(path.into()), svg, size));
+        self
+    }
+
+    /// Adds the element of the SVG with the given `id` at the given size in pixels, to be looked
+    /// up by its `id`. See [`Svg::rasterize_element`] for how the element is rendered.
+    ///
+    /// Elements are only looked up by their `id`, so elements of different SVGs need different
+    /// ids to be added to the same atlas.
+    pub fn add_element(&mut self, svg: &'a Svg, id: impl Into<String>, size: UVec2) -> &mut Self {
+        self.entries
+            .push((SvgAtlasKey::Element(id.into()), svg, size));
+        self
+    }
+
+    /// Rasterizes all SVGs and packs them into an atlas. The indices of the SVGs in the
+    /// [`TextureAtlasLayout`] are in the order they were added.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an element doesn't exist, if elements with the same id were added, or
+    /// if the SVGs don't fit into an atlas of the maximum size.
+    pub fn build(&self) -> Result<SvgAtlas, SvgAtlasError> {
+        debug!("Building atlas of {} SVGs ...", self.entries.len());
+        let mut paths = HashMap::new();
+        let mut elements = HashMap::new();
+        let mut images = Vec::with_capacity(self.entries.len());
+        for (index, (key, svg, size)) in self.entries.iter().enumerate() {
+            match key {
+                SvgAtlasKey::Path(path) => {
+                    images.push(svg.rasterize_with_aspect_ratio(
+                        size.x,
+                        size.y,
+                        svg.preserve_aspect_ratio,
+                    ));
+                    paths.insert(path.clone(), index);
+                }
+                SvgAtlasKey::Element(id) => {
+                    if elements.contains_key(id) {
+                        return Err(SvgAtlasError::DuplicateElement {
+                            id: id.clone(),
+                            svg: svg.name.clone(),
+                        });
+                    }
+                    let image = svg.rasterize_element(id, size.x, size.y).ok_or_else(|| {
+                        SvgAtlasError::UnknownElement {
+                            id: id.clone(),
+                            svg: svg.name.clone(),
+                        }
+                    })?;
+                    images.push(image);
+                    elements.insert(id.clone(), index);
+                }
+            }
+        }
+
+        let mut builder = TextureAtlasBuilder::default();
+        builder
+            .initial_size(self.initial_size)
+            .max_size(self.max_size)
+            .padding(self.padding);
+        for image in &images {
+            builder.add_texture(None, image);
+        }
+        let (layout, _, image) = builder.build()?;
+        debug!("Building atlas of {} SVGs ... Done", self.entries.len());
+
+        Ok(SvgAtlas {
+            layout,
+            image,
+            paths,
+            elements,
+        })
+    }
+}
+
+/// SVGs rasterized into a texture atlas by an [`SvgAtlasBuilder`].
+#[derive(Clone, Debug)]
+pub struct SvgAtlas {
+    /// Where the SVGs are in the [`image`](SvgAtlas::image).
+    pub layout: TextureAtlasLayout,
+    /// The image all SVGs are rasterized into.
+    pub image: Image,
+    paths: HashMap<AssetPath<'static>, usize>,
+    elements: HashMap<String, usize>,
+}
+
+impl SvgAtlas {
+    /// Index in the [`layout`](SvgAtlas::layout) of the SVG that was added with this path.
+    pub fn index_of_path<'b>(&self, path: impl Into<AssetPath<'b>>) -> Option<usize> {
+        self.paths.get(&path.into().into_owned()).copied()
+    }
+
+    /// Index in the [`layout`](SvgAtlas::layout) of the element that was added with this `id`.
+    pub fn index_of_element(&self, id: &str) -> Option<usize> {
+        self.elements.get(id).copied()
+    }
+
+    /// The [`TextureAtlas`] for the SVG that was added with this path, once the
+    /// [`layout`](SvgAtlas::layout) was added as the asset behind `layout`.
+    pub fn path_texture_atlas<'b>(
+        &self,
+        layout: Handle<TextureAtlasLayout>,
+        path: impl Into<AssetPath<'b>>,
+    ) -> Option<TextureAtlas> {
+        let index = self.index_of_path(path)?;
+        Some(TextureAtlas { layout, index })
+    }
+
+    /// The [`TextureAtlas`] for the element that was added with this `id`, once the
+    /// [`layout`](SvgAtlas::layout) was added as the asset behind `layout`.
+    pub fn element_texture_atlas(
+        &self,
+        layout: Handle<TextureAtlasLayout>,
+        id: &str,
+    ) -> Option<TextureAtlas> {
+        let index = self.index_of_element(id)?;
+        Some(TextureAtlas { layout, index })
+    }
+}
+
+/// An error that occurs when building an [`SvgAtlas`].
+#[derive(Error, Debug)]
+pub enum SvgAtlasError {
+    /// An element that was added doesn't exist or is empty, or the SVG doesn't have its
+    /// document anymore.
+    #[error("the element with id `{id}` in SVG `{svg}` doesn't exist or is empty")]
+    UnknownElement {
+        /// The id of the element.
+        id: String,
+        /// The name of the SVG.
+        svg: String,
+    },
+    /// An element was added with the same id as another one, which it would hide when the
+    /// elements are looked up by their id.
+    #[error("an element with id `{id}` was added before the one in SVG `{svg}`")]
+    DuplicateElement {
+        /// The id of the element.
+        id: String,
+        /// The name of the SVG the element was added from last.
+        svg: String,
+    },
+    /// The SVGs don't fit into an atlas.
+    #[error("could not build texture atlas: {0}")]
+    Atlas(#[from] TextureAtlasBuilderError),
+}
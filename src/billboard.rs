@@ -0,0 +1,149 @@
+//! Billboards, [`Svg3d`]s that always face the camera.
+
+use bevy::{
+    asset::Assets,
+    core_pipeline::core_3d::Camera3d,
+    ecs::{
+        component::Component,
+        query::{With, Without},
+        system::{Query, Res},
+    },
+    hierarchy::Parent,
+    math::{Quat, Rect, Vec3Swizzles},
+    render::camera::Camera,
+    transform::components::{GlobalTransform, Transform},
+};
+
+use crate::{
+    origin::{Origin, OriginBounds},
+    render::Svg3d,
+    svg::Svg,
+};
+
+/// How a [`Svg3dBillboard`] is rotated to face the camera.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BillboardMode {
+    /// The SVG is rotated freely, so it always lies parallel to the screen.
+    #[default]
+    Spherical,
+    /// The SVG is only rotated around the world y-axis, so it stays upright. Useful for
+    /// nameplates and markers standing on the ground.
+    Cylindrical,
+}
+
+/// Makes an [`Svg3d`] always face the camera.
+///
+/// The rotation of the [`Transform`] is ignored, the SVG is rotated around its [`Origin`]. When
+/// there are multiple cameras, the active [`Camera3d`] with the highest order is faced.
+/// Children of the entity are not affected by the billboard rotation and scale.
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq)]
+#[require(Svg3d)]
+pub struct Svg3dBillboard {
+    /// How the SVG is rotated to face the camera.
+    pub mode: BillboardMode,
+    /// Keeps the SVG at the same size on screen, independent of its distance to the camera.
+    /// One unit of the SVG, scaled by the [`Transform`], is then one logical pixel.
+    pub fixed_size: bool,
+}
+
+impl Svg3dBillboard {
+    /// A billboard that is rotated freely to face the camera.
+    pub fn spherical() -> Self {
+        Self {
+            mode: BillboardMode::Spherical,
+            fixed_size: false,
+        }
+    }
+
+    /// A billboard that only rotates around the world y-axis to face the camera.
+    pub fn cylindrical() -> Self {
+        Self {
+            mode: BillboardMode::Cylindrical,
+            fixed_size: false,
+        }
+    }
+
+    /// Keeps the billboard at the same size on screen.
+    pub fn with_fixed_size(mut self) -> Self {
+        self.fixed_size = true;
+        self
+    }
+}
+
+/// Rotates all [`Svg3dBillboard`]s to face the camera, and scales them if they have a fixed size.
+///
+/// The [`GlobalTransform`] is computed from scratch every frame, so this needs to run after
+/// [`apply_origin`](crate::origin::apply_origin), which it replaces for billboards.
+pub fn apply_billboard(
+    svgs: Res<Assets<Svg>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut query: Query<
+        (
+            &Svg3d,
+            &Svg3dBillboard,
+            &Origin,
+            &OriginBounds,
+            &Transform,
+            Option<&Parent>,
+            &mut GlobalTransform,
+        ),
+        Without<Camera>,
+    >,
+    parents: Query<&GlobalTransform, Without<Svg3dBillboard>>,
+) {
+    let Some((camera, camera_transform)) = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .max_by_key(|(camera, _)| camera.order)
+    else {
+        return;
+    };
+    let clip_from_view = camera.clip_from_view();
+    let is_orthographic = clip_from_view.w_axis.w == 1.0;
+    let viewport_height = camera
+        .logical_viewport_size()
+        .map_or(1.0, |size| size.y.max(1.0));
+
+    for (svg_3d, billboard, origin, origin_bounds, transform, parent, mut global_transform) in
+        &mut query
+    {
+        let Some(svg) = svgs.get(&svg_3d.0) else {
+            continue;
+        };
+        let parent_transform = parent
+            .and_then(|parent| parents.get(parent.get()).ok())
+            .copied()
+            .unwrap_or_default();
+        let pivot = parent_transform.transform_point(transform.translation);
+        let mut scale = parent_transform.compute_transform().scale * transform.scale;
+
+        let rotation = match billboard.mode {
+            BillboardMode::Spherical => camera_transform.compute_transform().rotation,
+            BillboardMode::Cylindrical => {
+                let towards_camera = camera_transform.back();
+                Quat::from_rotation_y(towards_camera.x.atan2(towards_camera.z))
+            }
+        };
+
+        if billboard.fixed_size {
+            // Size of a logical pixel in world units, at the distance of the billboard
+            let distance = if is_orthographic {
+                1.0
+            } else {
+                (pivot - camera_transform.translation()).dot(*camera_transform.forward())
+            };
+            scale *= 2.0 * distance.max(0.0) / (clip_from_view.y_axis.y * viewport_height);
+        }
+
+        let bounds = svg.bounds(*origin_bounds);
+        let origin_translation = origin.compute_translation_in(Rect {
+            min: bounds.min * scale.xy(),
+            max: bounds.max * scale.xy(),
+        });
+        *global_transform = GlobalTransform::from(Transform {
+            translation: pivot + rotation * origin_translation,
+            rotation,
+            scale,
+        });
+    }
+}
@@ -0,0 +1,638 @@
+//! Building [`Svg`]s from shapes at runtime, without writing and parsing SVG text.
+
+use bevy::{
+    asset::Assets,
+    color::{Alpha, Color},
+    log::debug,
+    math::{Affine2, Rect, Vec2},
+    render::{alpha::AlphaMode, mesh::Mesh},
+};
+use lyon_path::{
+    geom::{euclid::Angle, Box2D},
+    math::{point, vector, Point},
+    Path, PathEvent, Polygon, Winding,
+};
+use lyon_tessellation::StrokeOptions;
+use std::sync::Arc;
+use svgtypes::{SimplePathSegment, SimplifyingPathParser, ViewBox};
+use thiserror::Error;
+use usvg::fontdb;
+
+use crate::svg::{default_fonts, DrawType, PathDescriptor, Svg};
+
+/// How a shape added to an [`SvgBuilder`] is filled and stroked.
+#[derive(Clone, Debug, Default)]
+pub struct ShapeStyle {
+    /// Color the shape is filled with, if any.
+    pub fill: Option<Color>,
+    /// Color and options the outline of the shape is stroked with, if any.
+    pub stroke: Option<(Color, StrokeOptions)>,
+}
+
+impl ShapeStyle {
+    /// A shape that is filled with `color`.
+    pub fn fill(color: impl Into<Color>) -> Self {
+        Self {
+            fill: Some(color.into()),
+            stroke: None,
+        }
+    }
+
+    /// A shape that is only stroked with `color`, `width` units wide.
+    pub fn stroke(color: impl Into<Color>, width: f32) -> Self {
+        Self::default().with_stroke(color, width)
+    }
+
+    /// Adds a stroke with `color`, `width` units wide.
+    #[must_use]
+    pub fn with_stroke(self, color: impl Into<Color>, width: f32) -> Self {
+        self.with_stroke_options(color, StrokeOptions::tolerance(0.01).with_line_width(width))
+    }
+
+    /// Adds a stroke with `color` and the given options, e.g. to set its caps and joins.
+    #[must_use]
+    pub fn with_stroke_options(mut self, color: impl Into<Color>, options: StrokeOptions) -> Self {
+        self.stroke = Some((color.into(), options));
+        self
+    }
+}
+
+impl<C: Into<Color>> From<C> for ShapeStyle {
+    fn from(color: C) -> Self {
+        Self::fill(color)
+    }
+}
+
+/// Builder that constructs an [`Svg`] from shapes, for content that is generated at runtime,
+/// like charts or minimaps.
+///
+/// Coordinates are in SVG units, with the y-axis pointing down, and shapes are drawn in the
+/// order they were added.
+///
+/// ```ignore
+/// let mut builder = SvgBuilder::new(Vec2::new(200.0, 100.0));
+/// builder
+///     .rect(Rect::new(0.0, 0.0, 200.0, 100.0), Color::WHITE)
+///     .circle(Vec2::new(50.0, 50.0), 40.0, ShapeStyle::fill(RED).with_stroke(BLACK, 2.0))
+///     .transform(Affine2::from_translation(Vec2::new(100.0, 0.0)))
+///     .path("M 0 90 L 40 20 L 80 90 Z", ShapeStyle::stroke(BLUE, 4.0))
+///     .text("Minimap", Vec2::new(10.0, 95.0), 12.0, Color::BLACK);
+/// let svg = builder.build_with_mesh(&mut meshes)?;
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct SvgBuilder {
+    name: String,
+    size: Vec2,
+    transform: Affine2,
+    font_family: Option<String>,
+    shapes: Vec<(Shape, ShapeStyle, Affine2)>,
+}
+
+/// A shape added to an [`SvgBuilder`], in its local coordinates.
+#[derive(Clone, Debug)]
+enum Shape {
+    Rect(Rect),
+    Ellipse {
+        center: Vec2,
+        radii: Vec2,
+    },
+    Polygon(Vec<Vec2>),
+    Path(String),
+    Text {
+        text: String,
+        position: Vec2,
+        font_size: f32,
+        font_family: Option<String>,
+    },
+}
+
+impl SvgBuilder {
+    /// Creates a builder for an SVG of the given size.
+    pub fn new(size: Vec2) -> Self {
+        Self {
+            name: String::new(),
+            size,
+            transform: Affine2::IDENTITY,
+            font_family: None,
+            shapes: Vec::new(),
+        }
+    }
+
+    /// Sets the name of the SVG.
+    pub fn name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the transform of the shapes that are added after it, like the `transform` attribute
+    /// of a group. Reset it with [`Affine2::IDENTITY`].
+    pub fn transform(&mut self, transform: Affine2) -> &mut Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Sets the font family of the text that is added after it. Without a family, or if it
+    /// isn't found, text is drawn in the default sans-serif font.
+    pub fn font_family(&mut self, family: impl Into<String>) -> &mut Self {
+        self.font_family = Some(family.into());
+        self
+    }
+
+    /// Adds a rectangle.
+    pub fn rect(&mut self, rect: Rect, style: impl Into<ShapeStyle>) -> &mut Self {
+        self.add(Shape::Rect(rect), style)
+    }
+
+    /// Adds a circle.
+    pub fn circle(&mut self, center: Vec2, radius: f32, style: impl Into<ShapeStyle>) -> &mut Self {
+        self.ellipse(center, Vec2::splat(radius), style)
+    }
+
+    /// Adds an ellipse with the given radii along the x- and y-axis.
+    pub fn ellipse(
+        &mut self,
+        center: Vec2,
+        radii: Vec2,
+        style: impl Into<ShapeStyle>,
+    ) -> &mut Self {
+        self.add(Shape::Ellipse { center, radii }, style)
+    }
+
+    /// Adds a closed polygon through the points.
+    pub fn polygon(
+        &mut self,
+        points: impl IntoIterator<Item = Vec2>,
+        style: impl Into<ShapeStyle>,
+    ) -> &mut Self {
+        self.add(Shape::Polygon(points.into_iter().collect()), style)
+    }
+
+    /// Adds a path from SVG path data, like the `d` attribute of a `<path>`. The data is parsed
+    /// when the SVG is built.
+    pub fn path(&mut self, data: impl Into<String>, style: impl Into<ShapeStyle>) -> &mut Self {
+        self.add(Shape::Path(data.into()), style)
+    }
+
+    /// Adds a line of text, starting at `position` on its baseline, `font_size` units high.
+    ///
+    /// The glyphs are placed one after another with their advance and kerning, without shaping,
+    /// so scripts that need ligatures or reordering are not drawn correctly.
+    pub fn text(
+        &mut self,
+        text: impl Into<String>,
+        position: Vec2,
+        font_size: f32,
+        style: impl Into<ShapeStyle>,
+    ) -> &mut Self {
+        let shape = Shape::Text {
+            text: text.into(),
+            position,
+            font_size,
+            font_family: self.font_family.clone(),
+        };
+        self.add(shape, style)
+    }
+
+    fn add(&mut self, shape: Shape, style: impl Into<ShapeStyle>) -> &mut Self {
+        self.shapes.push((shape, style.into(), self.transform));
+        self
+    }
+
+    /// Builds the SVG. Its [`mesh`](Svg::mesh) is not set, tessellate it with
+    /// [`Svg::tessellate`] or use [`SvgBuilder::build_with_mesh`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if path data can't be parsed, or if there is text but no font.
+    pub fn build(&self) -> Result<Svg, SvgBuilderError> {
+        debug!("Building SVG: {} ...", self.name);
+        let mut paths = Vec::with_capacity(self.shapes.len());
+        let mut content_bounds: Option<Rect> = None;
+        for (shape, style, transform) in &self.shapes {
+            let segments = shape.segments()?;
+
+            let stroke_width = style.stroke.map_or(0.0, |(_, options)| options.line_width);
+            if let Some(bounds) = transformed_bounds(&segments, *transform, stroke_width / 2.0) {
+                content_bounds = Some(content_bounds.map_or(bounds, |rect| rect.union(bounds)));
+            }
+
+            let abs_transform = usvg::Transform::from_row(
+                transform.matrix2.x_axis.x,
+                transform.matrix2.x_axis.y,
+                transform.matrix2.y_axis.x,
+                transform.matrix2.y_axis.y,
+                transform.translation.x,
+                transform.translation.y,
+            );
+            // Fills are drawn below strokes, like the default `paint-order` of SVG
+            if let Some(color) = style.fill {
+                paths.push(PathDescriptor {
+                    id: String::new(),
+                    segments: segments.clone(),
+                    color,
+                    draw_type: DrawType::Fill,
+                    abs_transform,
+                    is_stroke: false,
+                    non_scaling_stroke: false,
+                });
+            }
+            if let Some((color, options)) = style.stroke {
+                paths.push(PathDescriptor {
+                    id: String::new(),
+                    segments,
+                    color,
+                    draw_type: DrawType::Stroke(options),
+                    abs_transform,
+                    is_stroke: true,
+                    non_scaling_stroke: false,
+                });
+            }
+        }
+
+        let alpha_mode = if paths.iter().any(|path| path.color.alpha() < 1.0) {
+            AlphaMode::Blend
+        } else {
+            AlphaMode::Opaque
+        };
+        debug!("Building SVG: {} ... Done", self.name);
+
+        Ok(Svg {
+            name: self.name.clone(),
+            size: self.size,
+            view_box: ViewBox {
+                x: 0.0,
+                y: 0.0,
+                w: f64::from(self.size.x),
+                h: f64::from(self.size.y),
+            },
+            content_bounds: content_bounds.unwrap_or_default(),
+            paths: Arc::new(paths),
+            alpha_mode,
+            ..Default::default()
+        })
+    }
+
+    /// Builds the SVG and tessellates it into a mesh that is added to `meshes`, so it is ready
+    /// to be added to `Assets<Svg>` and spawned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if path data can't be parsed, or if there is text but no font.
+    pub fn build_with_mesh(&self, meshes: &mut Assets<Mesh>) -> Result<Svg, SvgBuilderError> {
+        let mut svg = self.build()?;
+        svg.mesh = meshes.add(svg.tessellate());
+        Ok(svg)
+    }
+}
+
+impl Shape {
+    /// The outline of the shape, in its local coordinates.
+    fn segments(&self) -> Result<Vec<PathEvent>, SvgBuilderError> {
+        let mut builder = Path::builder();
+        match self {
+            Self::Rect(rect) => {
+                builder.add_rectangle(
+                    &Box2D::new(point(rect.min.x, rect.min.y), point(rect.max.x, rect.max.y)),
+                    Winding::Positive,
+                );
+            }
+            Self::Ellipse { center, radii } => {
+                builder.add_ellipse(
+                    point(center.x, center.y),
+                    vector(radii.x, radii.y),
+                    Angle::zero(),
+                    Winding::Positive,
+                );
+            }
+            Self::Polygon(points) => {
+                let points: Vec<Point> = points.iter().map(|p| point(p.x, p.y)).collect();
+                if points.len() > 1 {
+                    builder.add_polygon(Polygon {
+                        points: &points,
+                        closed: true,
+                    });
+                }
+            }
+            Self::Path(data) => {
+                build_path_data(&mut builder, data)?;
+            }
+            Self::Text {
+                text,
+                position,
+                font_size,
+                font_family,
+            } => {
+                build_text(
+                    &mut builder,
+                    text,
+                    *position,
+                    *font_size,
+                    font_family.as_deref(),
+                )?;
+            }
+        }
+        Ok(builder.build().iter().collect())
+    }
+}
+
+/// Adds the subpaths of SVG path data to the builder.
+fn build_path_data(
+    builder: &mut lyon_path::path::Builder,
+    data: &str,
+) -> Result<(), SvgBuilderError> {
+    let to_point = |x: f64, y: f64| point(x as f32, y as f32);
+    let mut start = Point::zero();
+    let mut is_open = false;
+    for segment in SimplifyingPathParser::from(data) {
+        let segment = segment.map_err(|error| SvgBuilderError::PathData {
+            data: data.to_string(),
+            error,
+        })?;
+        match segment {
+            SimplePathSegment::MoveTo { x, y } => {
+                if is_open {
+                    builder.end(false);
+                    is_open = false;
+                }
+                start = to_point(x, y);
+                begin(builder, &mut is_open, start);
+            }
+            SimplePathSegment::LineTo { x, y } => {
+                begin(builder, &mut is_open, start);
+                builder.line_to(to_point(x, y));
+            }
+            SimplePathSegment::Quadratic { x1, y1, x, y } => {
+                begin(builder, &mut is_open, start);
+                builder.quadratic_bezier_to(to_point(x1, y1), to_point(x, y));
+            }
+            SimplePathSegment::CurveTo {
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => {
+                begin(builder, &mut is_open, start);
+                builder.cubic_bezier_to(to_point(x1, y1), to_point(x2, y2), to_point(x, y));
+            }
+            SimplePathSegment::ClosePath => {
+                if is_open {
+                    builder.end(true);
+                    is_open = false;
+                }
+            }
+        }
+    }
+    if is_open {
+        builder.end(false);
+    }
+    Ok(())
+}
+
+/// Begins a subpath at `start` if none is open, since after a `Z` the next segment continues
+/// from the start of the closed subpath.
+fn begin(builder: &mut lyon_path::path::Builder, is_open: &mut bool, start: Point) {
+    if !*is_open {
+        builder.begin(start);
+        *is_open = true;
+    }
+}
+
+/// Adds the outlines of the glyphs of the text to the builder.
+fn build_text(
+    builder: &mut lyon_path::path::Builder,
+    text: &str,
+    position: Vec2,
+    font_size: f32,
+    font_family: Option<&str>,
+) -> Result<(), SvgBuilderError> {
+    let fonts = default_fonts();
+    let mut families = Vec::with_capacity(2);
+    if let Some(family) = font_family {
+        families.push(fontdb::Family::Name(family));
+    }
+    families.push(fontdb::Family::SansSerif);
+    let id = fonts
+        .query(&fontdb::Query {
+            families: &families,
+            ..Default::default()
+        })
+        .or_else(|| fonts.faces().next().map(|face| face.id))
+        .ok_or_else(|| SvgBuilderError::NoFont {
+            text: text.to_string(),
+        })?;
+
+    fonts
+        .with_face_data(id, |data, index| {
+            let face = ttf_parser::Face::parse(data, index).ok()?;
+            let scale = font_size / f32::from(face.units_per_em());
+            let kerning = face
+                .tables()
+                .kern
+                .and_then(|kern| kern.subtables.into_iter().find(|table| table.horizontal));
+            let mut x = position.x;
+            let mut previous = None;
+            for c in text.chars() {
+                let Some(glyph) = face.glyph_index(c) else {
+                    continue;
+                };
+                if let (Some(previous), Some(kerning)) = (previous, &kerning) {
+                    let kern = kerning.glyphs_kerning(previous, glyph).unwrap_or(0);
+                    x += f32::from(kern) * scale;
+                }
+                let mut outline = GlyphOutline {
+                    builder: &mut *builder,
+                    origin: Vec2::new(x, position.y),
+                    scale,
+                    is_open: false,
+                };
+                face.outline_glyph(glyph, &mut outline);
+                // Fonts don't always close their last contour, which would stay open otherwise
+                ttf_parser::OutlineBuilder::close(&mut outline);
+                x += f32::from(face.glyph_hor_advance(glyph).unwrap_or(0)) * scale;
+                previous = Some(glyph);
+            }
+            Some(())
+        })
+        .flatten()
+        .ok_or_else(|| SvgBuilderError::NoFont {
+            text: text.to_string(),
+        })
+}
+
+/// Adds the outline of a glyph to a path builder, flipped into SVG coordinates.
+struct GlyphOutline<'a> {
+    builder: &'a mut lyon_path::path::Builder,
+    origin: Vec2,
+    scale: f32,
+    is_open: bool,
+}
+
+impl GlyphOutline<'_> {
+    fn point(&self, x: f32, y: f32) -> Point {
+        point(
+            self.origin.x + x * self.scale,
+            self.origin.y - y * self.scale,
+        )
+    }
+}
+
+impl ttf_parser::OutlineBuilder for GlyphOutline<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        if self.is_open {
+            self.builder.end(true);
+        }
+        self.builder.begin(self.point(x, y));
+        self.is_open = true;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.builder.line_to(self.point(x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.builder
+            .quadratic_bezier_to(self.point(x1, y1), self.point(x, y));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.builder
+            .cubic_bezier_to(self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+    }
+
+    fn close(&mut self) {
+        if self.is_open {
+            self.builder.end(true);
+            self.is_open = false;
+        }
+    }
+}
+
+/// Bounding box of the points of the segments, including control points, after they are
+/// transformed, grown by `margin` for strokes.
+fn transformed_bounds(segments: &[PathEvent], transform: Affine2, margin: f32) -> Option<Rect> {
+    let mut bounds: Option<Rect> = None;
+    let mut add = |p: Point| {
+        let p = transform.transform_point2(Vec2::new(p.x, p.y));
+        bounds = Some(bounds.map_or(Rect::from_corners(p, p), |rect| rect.union_point(p)));
+    };
+    for segment in segments {
+        match *segment {
+            PathEvent::Begin { at } => add(at),
+            PathEvent::Line { to, .. } => add(to),
+            PathEvent::Quadratic { ctrl, to, .. } => {
+                add(ctrl);
+                add(to);
+            }
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                add(ctrl1);
+                add(ctrl2);
+                add(to);
+            }
+            PathEvent::End { .. } => {}
+        }
+    }
+    let scale = transform.matrix2.determinant().abs().sqrt();
+    bounds.map(|rect| rect.inflate(margin * scale))
+}
+
+/// An error that occurs when building an [`Svg`] with an [`SvgBuilder`].
+#[derive(Error, Debug)]
+pub enum SvgBuilderError {
+    /// The data of a path is not valid SVG path data.
+    #[error("invalid path data `{data}`: {error}")]
+    PathData {
+        /// The path data.
+        data: String,
+        /// Why it is invalid.
+        error: svgtypes::Error,
+    },
+    /// There is no font to draw text with.
+    #[error("no font found to draw the text `{text}`")]
+    NoFont {
+        /// The text.
+        text: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_bounds() {
+        let style = || ShapeStyle::fill(Color::WHITE).with_stroke(Color::BLACK, 2.0);
+        let bounds = |builder: &mut SvgBuilder| builder.build().unwrap().content_bounds;
+
+        let rect = Rect::new(10.0, 20.0, 30.0, 40.0);
+        assert_eq!(
+            bounds(SvgBuilder::new(Vec2::splat(100.0)).rect(rect, style())),
+            rect.inflate(1.0)
+        );
+        // Fills don't add a margin
+        assert_eq!(
+            bounds(SvgBuilder::new(Vec2::splat(100.0)).rect(rect, Color::WHITE)),
+            rect
+        );
+        // The control points of the arcs of the circle lie on its bounding box
+        let circle = bounds(SvgBuilder::new(Vec2::splat(100.0)).circle(
+            Vec2::new(50.0, 50.0),
+            10.0,
+            style(),
+        ));
+        assert!(circle.min.abs_diff_eq(Vec2::splat(39.0), 1e-4));
+        assert!(circle.max.abs_diff_eq(Vec2::splat(61.0), 1e-4));
+        assert_eq!(
+            bounds(SvgBuilder::new(Vec2::splat(100.0)).path("M 10 10 L 90 10 L 50 80 Z", style())),
+            Rect::new(9.0, 9.0, 91.0, 81.0)
+        );
+        // The margin is scaled together with the shape
+        assert_eq!(
+            bounds(
+                SvgBuilder::new(Vec2::splat(100.0))
+                    .transform(Affine2::from_scale(Vec2::splat(2.0)))
+                    .rect(rect, style())
+            ),
+            Rect::new(20.0, 40.0, 60.0, 80.0).inflate(2.0)
+        );
+    }
+
+    #[test]
+    fn path_after_close() {
+        let segments = Shape::Path("M 10 10 L 20 10 L 20 20 Z L 10 20".to_string())
+            .segments()
+            .unwrap();
+        let begins = segments
+            .iter()
+            .filter_map(|segment| match segment {
+                PathEvent::Begin { at } => Some(*at),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        // The line after the `Z` starts a new subpath at the start of the closed one
+        assert_eq!(begins, [point(10.0, 10.0), point(10.0, 10.0)]);
+        assert_eq!(
+            segments.last(),
+            Some(&PathEvent::End {
+                last: point(10.0, 20.0),
+                first: point(10.0, 10.0),
+                close: false,
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_path_data() {
+        let result = SvgBuilder::new(Vec2::splat(100.0))
+            .path("M 10 10 L 20", Color::WHITE)
+            .build();
+        assert!(matches!(
+            result,
+            Err(SvgBuilderError::PathData { data, .. }) if data == "M 10 10 L 20"
+        ));
+    }
+}
@@ -0,0 +1,395 @@
+//! Export of tessellated [`Svg`]s into other formats.
+
+use std::{fs, io, path::Path};
+
+use bevy::log::debug;
+use lyon_tessellation::{FillTessellator, StrokeTessellator};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::{
+    render::{
+        tessellation::{self, TessellationSettings},
+        vertex_buffer::{BufferExt, VertexBuffers},
+    },
+    svg::{StrokeScaling, Svg, SvgAntiAliasing, SvgCurves},
+};
+
+/// Options for [`Svg::export_gltf`].
+#[derive(Clone, Debug, Default)]
+pub struct GltfExportOptions {
+    /// Creates a separate node, named by the SVG id, for every element of the SVG
+    /// instead of a single node containing the whole SVG.
+    pub nodes_per_element: bool,
+}
+
+/// An error that occurs when exporting an [`Svg`] to glTF.
+#[derive(Error, Debug)]
+pub enum GltfExportError {
+    /// The file could not be written.
+    #[error("could not write file: {0}")]
+    IoError(#[from] io::Error),
+    /// The glTF document could not be serialized.
+    #[error("could not serialize glTF: {0}")]
+    JsonError(#[from] serde_json::Error),
+    /// The geometry doesn't fit into a glTF buffer.
+    #[error("the SVG has too much geometry to be stored in a glTF buffer")]
+    TooLarge,
+}
+
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_CHUNK_JSON: u32 = 0x4E4F_534A;
+const GLB_CHUNK_BIN: u32 = 0x004E_4942;
+
+impl Svg {
+    /// Tessellates the SVG and writes the resulting geometry to a glTF file.
+    ///
+    /// The binary `.glb` format is used if `path` has a `glb` extension. Otherwise a `.gltf`
+    /// file is written, with its binary buffer in a `.bin` file next to it if the SVG has any
+    /// geometry.
+    pub fn export_gltf(
+        &self,
+        path: impl AsRef<Path>,
+        options: &GltfExportOptions,
+    ) -> Result<(), GltfExportError> {
+        let path = path.as_ref();
+        debug!("Exporting SVG `{}` to {} ...", self.name, path.display());
+
+        let settings = TessellationSettings {
+            tolerance: None,
+            // Strokes only keep their width on screen in the shader
+            stroke_scaling: StrokeScaling::Scaled,
+            // The fringe is expanded in the shader, so it has no use outside of Bevy
+            anti_aliasing: SvgAntiAliasing::None,
+            // Analytic curves are rendered by the shader as well
+            curves: SvgCurves::Flattened,
+        };
+        let mut fill_tess = FillTessellator::new();
+        let mut stroke_tess = StrokeTessellator::new();
+        let buffers = self.paths.iter().enumerate().map(|(idx, descriptor)| {
+            let buffer = tessellation::generate_path_buffer(
+                descriptor,
+                idx as f32 * self.depth_offset,
+                &settings,
+                &mut fill_tess,
+                &mut stroke_tess,
+            );
+            (idx, descriptor, buffer)
+        });
+        let elements = if options.nodes_per_element {
+            let mut elements: Vec<(String, VertexBuffers)> = Vec::new();
+            for (idx, descriptor, buffer) in buffers {
+                // Fill and stroke of the same element follow each other, so they share a node
+                match elements.last_mut() {
+                    Some((id, buffers)) if !descriptor.id.is_empty() && *id == descriptor.id => {
+                        buffers.extend_one(buffer);
+                    }
+                    _ if descriptor.id.is_empty() => elements.push((format!("path{idx}"), buffer)),
+                    _ => elements.push((descriptor.id.clone(), buffer)),
+                }
+            }
+            elements
+        } else {
+            let mut merged = VertexBuffers::new();
+            for (.., buffer) in buffers {
+                merged.extend_one(buffer);
+            }
+            vec![(self.name.clone(), merged)]
+        };
+
+        let is_binary = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("glb"));
+        let bin_uri = (!is_binary).then(|| path.with_extension("bin"));
+        let (document, bin) = build_document(
+            &self.name,
+            &elements,
+            bin_uri
+                .as_ref()
+                .and_then(|uri| uri.file_name())
+                .map(|name| name.to_string_lossy().into_owned()),
+        )?;
+
+        if let Some(bin_uri) = bin_uri {
+            // The document doesn't reference a buffer without geometry
+            if !bin.is_empty() {
+                fs::write(&bin_uri, &bin)?;
+            }
+            fs::write(path, serde_json::to_vec_pretty(&document)?)?;
+        } else {
+            fs::write(path, to_glb(&document, bin)?)?;
+        }
+
+        debug!(
+            "Exporting SVG `{}` to {} ... Done",
+            self.name,
+            path.display()
+        );
+        Ok(())
+    }
+}
+
+/// Builds the glTF JSON document and its binary buffer.
+fn build_document(
+    name: &str,
+    elements: &[(String, VertexBuffers)],
+    bin_uri: Option<String>,
+) -> Result<(Value, Vec<u8>), GltfExportError> {
+    let mut bin = Vec::new();
+    let mut buffer_views = Vec::new();
+    let mut accessors = Vec::new();
+    let mut meshes = Vec::new();
+    let mut nodes = vec![json!({ "name": name })];
+    let mut children = Vec::new();
+
+    let mut push_view =
+        |bin: &mut Vec<u8>, data: &[u8], target: u32| -> Result<usize, GltfExportError> {
+            let offset = u32::try_from(bin.len()).map_err(|_| GltfExportError::TooLarge)?;
+            let length = u32::try_from(data.len()).map_err(|_| GltfExportError::TooLarge)?;
+            bin.extend_from_slice(data);
+            buffer_views.push(json!({
+                "buffer": 0,
+                "byteOffset": offset,
+                "byteLength": length,
+                "target": target,
+            }));
+            Ok(buffer_views.len() - 1)
+        };
+
+    for (id, buffers) in elements {
+        if buffers.indices.is_empty() {
+            continue;
+        }
+
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        let mut positions = Vec::with_capacity(buffers.vertices.len() * 12);
+        let mut colors = Vec::with_capacity(buffers.vertices.len() * 16);
+        for vertex in &buffers.vertices {
+            // Same as for the Bevy mesh, glTF has its y-axis pointing up
+            let position = [vertex.position[0], -vertex.position[1], vertex.position[2]];
+            for axis in 0..3 {
+                min[axis] = min[axis].min(position[axis]);
+                max[axis] = max[axis].max(position[axis]);
+            }
+            position
+                .iter()
+                .for_each(|x| positions.extend_from_slice(&x.to_le_bytes()));
+            vertex
+                .color
+                .iter()
+                .for_each(|x| colors.extend_from_slice(&x.to_le_bytes()));
+        }
+        let indices = buffers
+            .indices
+            .iter()
+            .flat_map(|idx| idx.to_le_bytes())
+            .collect::<Vec<_>>();
+
+        let count = buffers.vertices.len();
+        let position_view = push_view(&mut bin, &positions, ARRAY_BUFFER)?;
+        let color_view = push_view(&mut bin, &colors, ARRAY_BUFFER)?;
+        let index_view = push_view(&mut bin, &indices, ELEMENT_ARRAY_BUFFER)?;
+
+        accessors.push(json!({
+            "bufferView": position_view,
+            "componentType": FLOAT,
+            "count": count,
+            "type": "VEC3",
+            "min": min,
+            "max": max,
+        }));
+        accessors.push(json!({
+            "bufferView": color_view,
+            "componentType": FLOAT,
+            "count": count,
+            "type": "VEC4",
+        }));
+        accessors.push(json!({
+            "bufferView": index_view,
+            "componentType": UNSIGNED_INT,
+            "count": buffers.indices.len(),
+            "type": "SCALAR",
+        }));
+        let accessor = accessors.len() - 3;
+
+        meshes.push(json!({
+            "name": id,
+            "primitives": [{
+                "attributes": {
+                    "POSITION": accessor,
+                    "COLOR_0": accessor + 1,
+                },
+                "indices": accessor + 2,
+            }],
+        }));
+        nodes.push(json!({ "name": id, "mesh": meshes.len() - 1 }));
+        children.push(nodes.len() - 1);
+    }
+    if !children.is_empty() {
+        nodes[0]["children"] = children.into();
+    }
+
+    let mut buffer = json!({ "byteLength": bin.len() });
+    if let Some(uri) = bin_uri {
+        buffer["uri"] = uri.into();
+    }
+
+    let mut document = json!({
+        "asset": {
+            "version": "2.0",
+            "generator": concat!("bevy_svg ", env!("CARGO_PKG_VERSION")),
+        },
+        "scene": 0,
+        "scenes": [{ "name": name, "nodes": [0] }],
+        "nodes": nodes,
+        "meshes": meshes,
+        "accessors": accessors,
+        "bufferViews": buffer_views,
+        "buffers": [buffer],
+    });
+    if bin.is_empty() {
+        // glTF doesn't allow empty arrays or buffers, so an SVG without geometry only gets its root node
+        if let Some(document) = document.as_object_mut() {
+            document.retain(|key, _| {
+                !matches!(
+                    key.as_str(),
+                    "meshes" | "accessors" | "bufferViews" | "buffers"
+                )
+            });
+        }
+    }
+
+    Ok((document, bin))
+}
+
+/// Packs a glTF document and its binary buffer into the `.glb` container format.
+fn to_glb(document: &Value, mut bin: Vec<u8>) -> Result<Vec<u8>, GltfExportError> {
+    let mut json = serde_json::to_vec(document)?;
+    // Both chunks need to be aligned to 4 bytes
+    json.resize(json.len().next_multiple_of(4), b' ');
+    bin.resize(bin.len().next_multiple_of(4), 0);
+
+    // The BIN chunk is optional, and left out without geometry, like the buffer in the document
+    let mut chunks = vec![(GLB_CHUNK_JSON, json)];
+    if !bin.is_empty() {
+        chunks.push((GLB_CHUNK_BIN, bin));
+    }
+
+    let length = 12
+        + chunks
+            .iter()
+            .map(|(_, chunk)| 8 + chunk.len())
+            .sum::<usize>();
+    let length = u32::try_from(length).map_err(|_| GltfExportError::TooLarge)?;
+
+    let mut glb = Vec::with_capacity(length as usize);
+    glb.extend_from_slice(&GLB_MAGIC.to_le_bytes());
+    glb.extend_from_slice(&2u32.to_le_bytes());
+    glb.extend_from_slice(&length.to_le_bytes());
+    for (chunk_type, chunk) in chunks {
+        // chunk lengths are already checked by the total length above
+        glb.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&chunk_type.to_le_bytes());
+        glb.extend_from_slice(&chunk);
+    }
+
+    Ok(glb)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::mesh::{Indices, Mesh, VertexAttributeValues};
+
+    use super::*;
+
+    const SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50">
+        <path id="fill" d="M 10 10 L 90 10 Q 95 25 90 40 L 10 40 Z" fill="#ff8000"/>
+        <path id="stroke" d="M 20 25 L 80 25" fill="none" stroke="#0080ff" stroke-width="3"/>
+    </svg>"##;
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn glb_round_trip() {
+        let svg: Svg = SVG.parse().unwrap();
+        let path = std::env::temp_dir().join(format!("bevy_svg_export_{}.glb", std::process::id()));
+        svg.export_gltf(&path, &GltfExportOptions::default())
+            .unwrap();
+        let glb = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // Header
+        assert_eq!(u32_at(&glb, 0), GLB_MAGIC);
+        assert_eq!(u32_at(&glb, 4), 2);
+        assert_eq!(u32_at(&glb, 8) as usize, glb.len());
+
+        // Chunks, aligned to 4 bytes and filling the rest of the file
+        let mut chunks = Vec::new();
+        let mut offset = 12;
+        while offset < glb.len() {
+            let length = u32_at(&glb, offset) as usize;
+            assert_eq!(length % 4, 0);
+            chunks.push((
+                u32_at(&glb, offset + 4),
+                &glb[offset + 8..offset + 8 + length],
+            ));
+            offset += 8 + length;
+        }
+        assert_eq!(offset, glb.len());
+        let [(GLB_CHUNK_JSON, json), (GLB_CHUNK_BIN, bin)] = chunks[..] else {
+            panic!("expected a JSON and a BIN chunk");
+        };
+        let document: Value = serde_json::from_slice(json).unwrap();
+        // The BIN chunk is the buffer, padded to 4 bytes
+        let byte_length = document["buffers"][0]["byteLength"].as_u64().unwrap() as usize;
+        assert!(byte_length <= bin.len() && bin.len() - byte_length < 4);
+
+        // Accessors of the single mesh, against the mesh the SVG is rendered with
+        let mesh = svg.tessellate();
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("mesh without positions");
+        };
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("mesh without indices");
+        };
+        let min = positions.iter().fold([f32::MAX; 3], |min, position| {
+            [0, 1, 2].map(|axis| min[axis].min(position[axis]))
+        });
+        let max = positions.iter().fold([f32::MIN; 3], |max, position| {
+            [0, 1, 2].map(|axis| max[axis].max(position[axis]))
+        });
+
+        let primitive = &document["meshes"][0]["primitives"][0];
+        let accessor = |name: &str| {
+            &document["accessors"][primitive["attributes"][name].as_u64().unwrap() as usize]
+        };
+        let position = accessor("POSITION");
+        assert_eq!(position["count"], positions.len());
+        assert_eq!(position["min"], json!(min));
+        assert_eq!(position["max"], json!(max));
+        assert_eq!(accessor("COLOR_0")["count"], positions.len());
+        let index = &document["accessors"][primitive["indices"].as_u64().unwrap() as usize];
+        assert_eq!(index["count"], indices.len());
+
+        // The positions in the buffer are the ones of the mesh
+        let view = &document["bufferViews"][position["bufferView"].as_u64().unwrap() as usize];
+        let offset = view["byteOffset"].as_u64().unwrap() as usize;
+        let length = view["byteLength"].as_u64().unwrap() as usize;
+        assert_eq!(length, positions.len() * 12);
+        let buffer_positions = bin[offset..offset + length]
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(buffer_positions, positions.concat());
+    }
+}
@@ -0,0 +1,520 @@
+//! Extrusion of the fills of an [`Svg`] into solid 3D meshes.
+
+use bevy::{
+    color::ColorToComponents,
+    log::error,
+    math::{Vec2, Vec3},
+    render::{
+        mesh::{Indices, Mesh},
+        render_asset::RenderAssetUsages,
+        render_resource::PrimitiveTopology,
+    },
+};
+use lyon_path::{iterator::PathIterator, math::Point, PathEvent};
+use lyon_tessellation::{
+    BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex, VertexBuffers,
+};
+
+use crate::{
+    svg::{DrawType, Svg},
+    util::geometry::is_inside,
+};
+
+/// How the normals of the sides of an extruded [`Svg`] are computed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SideNormals {
+    /// Every side face has its own normal, giving the sides a faceted look.
+    #[default]
+    Flat,
+    /// Normals are averaged between neighbouring side faces, giving curved outlines a smooth look.
+    Smooth,
+}
+
+/// Options for [`Svg::extrude`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExtrudeOptions {
+    /// Total depth of the extruded solid along the z-axis, centered around `z = 0`.
+    pub depth: f32,
+    /// Size of the 45° chamfer along the edges of the caps, `0.0` disables the bevel.
+    ///
+    /// The outlines are inset by this amount, so it should stay small compared to the
+    /// features of the SVG. It is limited to half the depth, and for every fill to the inset at
+    /// which an edge of its outline shrinks to nothing.
+    pub bevel: f32,
+    /// Whether the front cap, facing `+z`, is generated.
+    pub front_cap: bool,
+    /// Whether the back cap, facing `-z`, is generated.
+    pub back_cap: bool,
+    /// How the normals of the sides are computed.
+    pub side_normals: SideNormals,
+    /// Tolerance used to flatten curves into line segments.
+    pub tolerance: f32,
+}
+
+impl Default for ExtrudeOptions {
+    fn default() -> Self {
+        Self {
+            depth: 10.0,
+            bevel: 0.0,
+            front_cap: true,
+            back_cap: true,
+            side_normals: SideNormals::default(),
+            tolerance: 0.01,
+        }
+    }
+}
+
+impl Svg {
+    /// Extrudes all fills of the SVG into a solid 3D [`Mesh`] with positions, normals and
+    /// vertex colors, which can be used together with a `StandardMaterial`.
+    ///
+    /// Strokes are ignored. Like the mesh of the SVG, the extruded mesh uses the Bevy
+    /// coordinate system with the top left corner of the SVG at the origin.
+    pub fn extrude(&self, options: &ExtrudeOptions) -> Mesh {
+        let mut builder = SolidBuilder::default();
+        let mut fill_tess = FillTessellator::new();
+
+        let half_depth = options.depth.abs() * 0.5;
+        let bevel = options.bevel.clamp(0.0, half_depth);
+
+        for path in self
+            .paths
+            .iter()
+            .filter(|path| matches!(path.draw_type, DrawType::Fill))
+        {
+            let transform = path.abs_transform;
+            let contours = flatten(
+                path.segments.iter().copied().flattened(options.tolerance),
+                |point| {
+                    let mut point = usvg::tiny_skia_path::Point::from_xy(point.x, point.y);
+                    transform.map_point(&mut point);
+                    // Bevy has a different y-axis origin, so we need to flip that axis
+                    Vec2::new(point.x, -point.y)
+                },
+            );
+            if contours.is_empty() {
+                continue;
+            }
+            builder.color = path.color.to_linear().to_f32_array();
+
+            let outlines = contours
+                .iter()
+                .map(|contour| Outline::new(contour, &contours))
+                .collect::<Vec<_>>();
+            // The bevel can't be wider than the narrowest part of the fill
+            let bevel = outlines
+                .iter()
+                .map(Outline::max_inset)
+                .fold(bevel, f32::min);
+
+            // Caps
+            let caps = if bevel > 0.0 {
+                outlines
+                    .iter()
+                    .map(|outline| outline.inset(bevel))
+                    .collect()
+            } else {
+                contours.clone()
+            };
+            let cap_buffers = tessellate_cap(&mut fill_tess, &caps);
+            if options.front_cap {
+                builder.cap(&cap_buffers, half_depth, Vec3::Z);
+            }
+            if options.back_cap {
+                builder.cap(&cap_buffers, -half_depth, Vec3::NEG_Z);
+            }
+
+            // Sides and bevels
+            for (outline, cap) in outlines.iter().zip(&caps) {
+                let count = outline.points.len();
+                for i in 0..count {
+                    let j = (i + 1) % count;
+                    let (normal_i, normal_j) = match options.side_normals {
+                        SideNormals::Flat => (outline.edge_normals[i], outline.edge_normals[i]),
+                        SideNormals::Smooth => (outline.normals[i], outline.normals[j]),
+                    };
+                    let side_z = half_depth - bevel;
+                    builder.quad(
+                        [
+                            outline.points[i].extend(-side_z),
+                            outline.points[j].extend(-side_z),
+                            outline.points[j].extend(side_z),
+                            outline.points[i].extend(side_z),
+                        ],
+                        [
+                            normal_i.extend(0.0),
+                            normal_j.extend(0.0),
+                            normal_j.extend(0.0),
+                            normal_i.extend(0.0),
+                        ],
+                        outline.edge_normals[i].extend(0.0),
+                    );
+
+                    if bevel > 0.0 {
+                        for z in [1.0, -1.0] {
+                            let bevel_normal = |normal: Vec2| normal.extend(z).normalize();
+                            builder.quad(
+                                [
+                                    outline.points[i].extend(z * side_z),
+                                    outline.points[j].extend(z * side_z),
+                                    cap[j].extend(z * half_depth),
+                                    cap[i].extend(z * half_depth),
+                                ],
+                                [
+                                    bevel_normal(normal_i),
+                                    bevel_normal(normal_j),
+                                    bevel_normal(normal_j),
+                                    bevel_normal(normal_i),
+                                ],
+                                bevel_normal(outline.edge_normals[i]),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        builder.build()
+    }
+}
+
+/// Splits flattened path events into closed contours of points.
+fn flatten(events: impl Iterator<Item = PathEvent>, map: impl Fn(Point) -> Vec2) -> Vec<Vec<Vec2>> {
+    let mut contours = Vec::new();
+    let mut contour: Vec<Vec2> = Vec::new();
+
+    let push = |contour: &mut Vec<Vec2>, point: Vec2| {
+        if contour
+            .last()
+            .map_or(true, |last| last.distance_squared(point) > f32::EPSILON)
+        {
+            contour.push(point);
+        }
+    };
+    for event in events {
+        match event {
+            PathEvent::Begin { at } => {
+                contour.clear();
+                push(&mut contour, map(at));
+            }
+            PathEvent::Line { to, .. } => push(&mut contour, map(to)),
+            PathEvent::End { .. } => {
+                // Fills are always closed, so a duplicated start point isn't needed
+                if contour.len() > 1
+                    && contour[0].distance_squared(contour[contour.len() - 1]) <= f32::EPSILON
+                {
+                    contour.pop();
+                }
+                if contour.len() >= 3 {
+                    contours.push(std::mem::take(&mut contour));
+                }
+                contour.clear();
+            }
+            // `flattened` only emits lines
+            PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => {}
+        }
+    }
+
+    contours
+}
+
+/// A closed contour with normals pointing away from the filled area.
+struct Outline {
+    points: Vec<Vec2>,
+    /// Normal of the edge from point `i` to point `i + 1`.
+    edge_normals: Vec<Vec2>,
+    /// Averaged normal at point `i`.
+    normals: Vec<Vec2>,
+}
+
+impl Outline {
+    fn new(points: &[Vec2], contours: &[Vec<Vec2>]) -> Self {
+        let count = points.len();
+        let edge = |i: usize| points[(i + 1) % count] - points[i];
+        let mut edge_normals = (0..count)
+            .map(|i| {
+                let edge = edge(i);
+                Vec2::new(edge.y, -edge.x).normalize_or_zero()
+            })
+            .collect::<Vec<_>>();
+
+        // Holes and outer contours can have any winding, so we check on which side of the
+        // longest edge the filled area lies.
+        let longest = (0..count)
+            .max_by(|a, b| {
+                edge(*a)
+                    .length_squared()
+                    .total_cmp(&edge(*b).length_squared())
+            })
+            .unwrap_or_default();
+        let midpoint = points[longest] + edge(longest) * 0.5;
+        let probe = midpoint + edge_normals[longest] * edge(longest).length() * 1e-3;
+        if is_inside(probe, contours) {
+            edge_normals
+                .iter_mut()
+                .for_each(|normal| *normal = -*normal);
+        }
+
+        let normals = (0..count)
+            .map(|i| {
+                let prev = edge_normals[(i + count - 1) % count];
+                let next = edge_normals[i];
+                (prev + next).try_normalize().unwrap_or(next)
+            })
+            .collect();
+
+        Self {
+            points: points.to_vec(),
+            edge_normals,
+            normals,
+        }
+    }
+
+    /// Direction in which point `i` moves when the outline is inset by one unit.
+    fn inset_direction(&self, i: usize) -> Vec2 {
+        // Limit the miter so sharp corners don't shoot off
+        let miter = self.normals[i].dot(self.edge_normals[i]).max(0.25);
+        -self.normals[i] / miter
+    }
+
+    /// Moves every point inwards by `distance`, keeping the edges parallel.
+    fn inset(&self, distance: f32) -> Vec<Vec2> {
+        (0..self.points.len())
+            .map(|i| self.points[i] + self.inset_direction(i) * distance)
+            .collect()
+    }
+
+    /// The largest distance the outline can be inset by before one of its edges shrinks to
+    /// nothing and the inset turns inside out.
+    fn max_inset(&self) -> f32 {
+        let count = self.points.len();
+        (0..count)
+            .filter_map(|i| {
+                let j = (i + 1) % count;
+                let edge = self.points[j] - self.points[i];
+                // How fast the edge gets shorter while the outline is inset
+                let shrink = edge
+                    .normalize_or_zero()
+                    .dot(self.inset_direction(i) - self.inset_direction(j));
+                (shrink > 0.0).then(|| edge.length() / shrink)
+            })
+            .fold(f32::INFINITY, f32::min)
+    }
+}
+
+/// Tessellates the area enclosed by `contours`.
+fn tessellate_cap(
+    fill_tess: &mut FillTessellator,
+    contours: &[Vec<Vec2>],
+) -> VertexBuffers<Vec2, u32> {
+    let to_point = |point: Vec2| Point::new(point.x, point.y);
+    let events = contours.iter().flat_map(|contour| {
+        let first = to_point(contour[0]);
+        let last = to_point(contour[contour.len() - 1]);
+        std::iter::once(PathEvent::Begin { at: first })
+            .chain(contour.windows(2).map(move |pair| PathEvent::Line {
+                from: to_point(pair[0]),
+                to: to_point(pair[1]),
+            }))
+            .chain(std::iter::once(PathEvent::End {
+                last,
+                first,
+                close: true,
+            }))
+    });
+
+    let mut buffers: VertexBuffers<Vec2, u32> = VertexBuffers::new();
+    if let Err(e) = fill_tess.tessellate(
+        events,
+        &FillOptions::tolerance(0.001).with_fill_rule(FillRule::EvenOdd),
+        &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| {
+            Vec2::new(vertex.position().x, vertex.position().y)
+        }),
+    ) {
+        error!("FillTessellator error: {:?}", e);
+    }
+
+    buffers
+}
+
+/// Collects the geometry of the extruded solid.
+#[derive(Default)]
+struct SolidBuilder {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+    color: [f32; 4],
+}
+
+impl SolidBuilder {
+    fn vertex(&mut self, position: Vec3, normal: Vec3) -> u32 {
+        self.positions.push(position.to_array());
+        self.normals.push(normal.to_array());
+        self.colors.push(self.color);
+        self.positions.len() as u32 - 1
+    }
+
+    /// Adds a triangle, making sure it is wound counter-clockwise when seen from `facing`.
+    fn triangle(&mut self, [a, b, c]: [u32; 3], facing: Vec3) {
+        let [pa, pb, pc] = [a, b, c].map(|idx| Vec3::from_array(self.positions[idx as usize]));
+        if (pb - pa).cross(pc - pa).dot(facing) < 0.0 {
+            self.indices.extend([a, c, b]);
+        } else {
+            self.indices.extend([a, b, c]);
+        }
+    }
+
+    fn cap(&mut self, buffers: &VertexBuffers<Vec2, u32>, z: f32, normal: Vec3) {
+        let offset = self.positions.len() as u32;
+        for point in &buffers.vertices {
+            self.vertex(point.extend(z), normal);
+        }
+        for triangle in buffers.indices.chunks_exact(3) {
+            self.triangle(
+                [triangle[0], triangle[1], triangle[2]].map(|idx| idx + offset),
+                normal,
+            );
+        }
+    }
+
+    fn quad(&mut self, positions: [Vec3; 4], normals: [Vec3; 4], facing: Vec3) {
+        let [a, b, c, d] = [0, 1, 2, 3].map(|i| self.vertex(positions[i], normals[i]));
+        self.triangle([a, b, c], facing);
+        self.triangle([a, c, d], facing);
+    }
+
+    fn build(self) -> Mesh {
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, self.positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, self.normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, self.colors);
+        mesh.insert_indices(Indices::U32(self.indices));
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::mesh::VertexAttributeValues;
+
+    use super::*;
+
+    const SQUARE: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+        <path d="M 0 0 L 10 0 L 10 10 L 0 10 Z"/>
+    </svg>"#;
+
+    /// Positions, normals and triangles of the mesh.
+    fn geometry(mesh: &Mesh) -> (Vec<Vec3>, Vec<Vec3>, Vec<[usize; 3]>) {
+        let attribute = |id| match mesh.attribute(id) {
+            Some(VertexAttributeValues::Float32x3(values)) => {
+                values.iter().copied().map(Vec3::from_array).collect()
+            }
+            _ => panic!("mesh without {id:?}"),
+        };
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("mesh without indices");
+        };
+        let triangles = indices
+            .chunks_exact(3)
+            .map(|triangle| [0, 1, 2].map(|idx| triangle[idx] as usize))
+            .collect();
+        (
+            attribute(Mesh::ATTRIBUTE_POSITION.id),
+            attribute(Mesh::ATTRIBUTE_NORMAL.id),
+            triangles,
+        )
+    }
+
+    /// Checks that every edge is shared by exactly two triangles, which run along it in opposite
+    /// directions, and that all faces and normals point away from the center of the solid.
+    fn assert_closed_and_outward(mesh: &Mesh, center: Vec3) {
+        let (positions, normals, triangles) = geometry(mesh);
+        // Vertices are duplicated between faces, so edges are compared by their positions
+        let key = |idx: usize| {
+            positions[idx]
+                .to_array()
+                .map(|x| (x * 1000.0).round() as i32)
+        };
+        let mut edges = std::collections::HashMap::new();
+        for triangle in &triangles {
+            for k in 0..3 {
+                let edge = (key(triangle[k]), key(triangle[(k + 1) % 3]));
+                *edges.entry(edge).or_insert(0) += 1;
+            }
+
+            let [a, b, c] = triangle.map(|idx| positions[idx]);
+            let face_normal = (b - a).cross(c - a);
+            let centroid = (a + b + c) / 3.0;
+            assert!(
+                face_normal.dot(centroid - center) > 0.0,
+                "triangle {triangle:?} faces inwards"
+            );
+            for idx in triangle {
+                assert!(
+                    normals[*idx].dot(face_normal) > 0.0,
+                    "normal of vertex {idx} points inwards"
+                );
+            }
+        }
+        for ((from, to), count) in &edges {
+            assert_eq!(*count, 1, "edge {from:?} -> {to:?} is used {count} times");
+            assert_eq!(
+                edges.get(&(*to, *from)),
+                Some(&1),
+                "edge {from:?} -> {to:?} is open"
+            );
+        }
+    }
+
+    #[test]
+    fn square() {
+        let svg: Svg = SQUARE.parse().unwrap();
+        let mesh = svg.extrude(&ExtrudeOptions::default());
+        let (positions, _, triangles) = geometry(&mesh);
+        // Two caps of 4 vertices and 2 triangles, and 4 sides of 4 vertices and 2 triangles
+        assert_eq!(positions.len(), 2 * 4 + 4 * 4);
+        assert_eq!(triangles.len(), 2 * 2 + 4 * 2);
+        assert_closed_and_outward(&mesh, Vec3::new(5.0, -5.0, 0.0));
+    }
+
+    #[test]
+    fn square_with_bevel() {
+        let svg: Svg = SQUARE.parse().unwrap();
+        let mesh = svg.extrude(&ExtrudeOptions {
+            bevel: 1.0,
+            side_normals: SideNormals::Smooth,
+            ..Default::default()
+        });
+        let (positions, _, triangles) = geometry(&mesh);
+        // Every side gets a bevel towards each cap
+        assert_eq!(positions.len(), 2 * 4 + 3 * 4 * 4);
+        assert_eq!(triangles.len(), 2 * 2 + 3 * 4 * 2);
+        assert_closed_and_outward(&mesh, Vec3::new(5.0, -5.0, 0.0));
+    }
+
+    #[test]
+    fn bevel_wider_than_square() {
+        let svg: Svg = SQUARE.parse().unwrap();
+        let mesh = svg.extrude(&ExtrudeOptions {
+            depth: 30.0,
+            bevel: 12.0,
+            ..Default::default()
+        });
+        let (positions, ..) = geometry(&mesh);
+        // The bevel is clamped to half the width, so the caps shrink to the center instead of
+        // turning inside out beyond the outline
+        for position in positions {
+            assert!(
+                (0.0..=10.0).contains(&position.x) && (-10.0..=0.0).contains(&position.y),
+                "{position} is outside of the square"
+            );
+            if position.z.abs() == 15.0 {
+                assert!(position.truncate().distance(Vec2::new(5.0, -5.0)) < 1e-4);
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Fitting SVGs into a size, following the rules of `preserveAspectRatio`.
+
+use bevy::{ecs::component::Component, math::Vec2};
+use svgtypes::{Align, AspectRatio};
+
+#[cfg(feature = "2d")]
+use bevy::ecs::query::Without;
+
+#[cfg(feature = "2d")]
+use crate::{
+    mapped_mesh::{SvgMappedMesh, SvgMeshMapping},
+    slice::SvgSlices,
+};
+use crate::{svg::Svg, util::aspect};
+
+/// Displays an `Svg2d` at the given size, in world units, without changing its `Transform`.
+///
+/// The SVG is fitted into the size by moving the vertices of its mesh, so the `Origin` is
+/// relative to the fitted SVG, and its `OriginBounds::Size` is the given size. Parts of the SVG
+/// that are outside of the size, when it is filled, are not clipped. The fitted SVG can be
+/// mirrored within the size as well, like a flipped `Sprite`. An `SvgLod` or `SvgSlices` on the
+/// same entity replaces the mesh as well, so `SvgSlices` takes precedence, and `SvgLod` is
+/// ignored.
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "2d", require(SvgMappedMesh<SvgFit>))]
+pub struct SvgFit {
+    /// Size the SVG is fitted into.
+    pub size: Vec2,
+    /// How the SVG is fitted into the size.
+    pub mode: SvgFitMode,
+    /// Mirrors the SVG horizontally.
+    pub flip_x: bool,
+    /// Mirrors the SVG vertically.
+    pub flip_y: bool,
+}
+
+/// How an [`SvgFit`] fits the SVG into its size.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SvgFitMode {
+    /// Follows the `preserveAspectRatio` of the SVG.
+    #[default]
+    AspectRatio,
+    /// Scales the SVG uniformly, until it fits into the size, and centers it. The same as
+    /// `preserveAspectRatio="xMidYMid meet"`.
+    Fit,
+    /// Scales the SVG uniformly, until it covers the size, and centers it. The same as
+    /// `preserveAspectRatio="xMidYMid slice"`.
+    Fill,
+    /// Scales the SVG non-uniformly to the size. The same as `preserveAspectRatio="none"`.
+    Stretch,
+}
+
+impl SvgFit {
+    /// Fits an SVG into the given size, following its `preserveAspectRatio`.
+    pub fn new(size: Vec2) -> Self {
+        Self {
+            size,
+            ..Default::default()
+        }
+    }
+
+    /// Fits an SVG into the given size, with the given mode.
+    pub fn with_mode(size: Vec2, mode: SvgFitMode) -> Self {
+        Self {
+            size,
+            mode,
+            ..Default::default()
+        }
+    }
+
+    /// The `preserveAspectRatio` the SVG is fitted with.
+    pub fn aspect_ratio(&self, svg: &Svg) -> AspectRatio {
+        let (align, slice) = match self.mode {
+            SvgFitMode::AspectRatio => return svg.preserve_aspect_ratio,
+            SvgFitMode::Fit => (Align::XMidYMid, false),
+            SvgFitMode::Fill => (Align::XMidYMid, true),
+            SvgFitMode::Stretch => (Align::None, false),
+        };
+        AspectRatio {
+            defer: false,
+            align,
+            slice,
+        }
+    }
+
+    /// Maps a point of the SVG, in SVG coordinates, to its position in the fitted SVG.
+    pub fn map_point(&self, point: Vec2, svg: &Svg) -> Vec2 {
+        let (scale, offset) = aspect::fit(
+            svg.size.max(Vec2::splat(f32::EPSILON)),
+            self.size,
+            self.aspect_ratio(svg),
+        );
+        let point = point * scale + offset;
+        Vec2::new(
+            if self.flip_x {
+                self.size.x - point.x
+            } else {
+                point.x
+            },
+            if self.flip_y {
+                self.size.y - point.y
+            } else {
+                point.y
+            },
+        )
+    }
+}
+
+#[cfg(feature = "2d")]
+impl SvgMeshMapping for SvgFit {
+    type Filter = Without<SvgSlices>;
+
+    fn map_mesh_point(&self, point: Vec2, svg: &Svg) -> Vec2 {
+        self.map_point(point, svg)
+    }
+}
@@ -12,6 +12,20 @@
 //!         .run();
 //! }
 //! ```
+//!
+//! ## Embedded SVGs
+//! SVGs are loaded from any asset source, so SVGs shipped inside the binary are loaded from
+//! `embedded://` paths, after registering them with `embedded_asset!`:
+//!
+//! ```ignore
+//! embedded_asset!(app, "icons/home.svg");
+//! // in a system
+//! let svg: Handle<Svg> = asset_server.load("embedded://my_crate/icons/home.svg");
+//! ```
+//!
+//! Fonts are not embedded with them, text is rendered with the system fonts and the fonts in
+//! `./assets`. SVGs that are generated at runtime are parsed from a string with
+//! [`Svg::from_str_with_mesh`](svg::Svg::from_str_with_mesh).
 
 // rustc
 #![deny(future_incompatible, nonstandard_style)]
@@ -26,26 +40,71 @@
     clippy::cargo
 )]
 
+#[cfg(all(feature = "2d", feature = "rasterize"))]
+mod atlas;
+#[cfg(feature = "3d")]
+mod billboard;
+mod builder;
+#[cfg(feature = "gltf")]
+mod export;
+mod extrude;
+mod fit;
 mod loader;
 #[cfg(any(feature = "2d", feature = "3d"))]
+mod lod;
+#[cfg(feature = "2d")]
+mod mapped_mesh;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod msdf;
+#[cfg(any(feature = "2d", feature = "3d"))]
 mod origin;
 #[cfg(any(feature = "2d", feature = "3d"))]
 mod plugin;
+mod processed;
+#[cfg(feature = "rasterize")]
+mod rasterize;
 mod render;
-mod resources;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod retessellation;
+mod slice;
+#[cfg(feature = "2d")]
+mod sprite;
 mod svg;
+#[cfg(feature = "ui")]
+mod ui;
 mod util;
 
 /// Import this module as `use bevy_svg::prelude::*` to get convenient imports.
 pub mod prelude {
     pub use super::{SvgPlugin, SvgSet};
+    #[cfg(all(feature = "2d", feature = "rasterize"))]
+    pub use crate::atlas::{SvgAtlas, SvgAtlasBuilder, SvgAtlasError};
+    #[cfg(feature = "3d")]
+    pub use crate::billboard::{BillboardMode, Svg3dBillboard};
+    pub use crate::builder::{ShapeStyle, SvgBuilder, SvgBuilderError};
+    #[cfg(feature = "gltf")]
+    pub use crate::export::{GltfExportError, GltfExportOptions};
+    pub use crate::extrude::{ExtrudeOptions, SideNormals};
+    pub use crate::fit::{SvgFit, SvgFitMode};
+    pub use crate::loader::SvgLoaderSettings;
     #[cfg(any(feature = "2d", feature = "3d"))]
-    pub use crate::origin::Origin;
+    pub use crate::lod::SvgLod;
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::origin::{Origin, OriginBounds};
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::render::SvgMsdfMaterial;
     #[cfg(feature = "2d")]
     pub use crate::render::{Svg2d, Svg2dBundle};
     #[cfg(feature = "3d")]
-    pub use crate::render::{Svg3d, Svg3dBundle};
-    pub use crate::svg::Svg;
+    pub use crate::render::{Svg3d, Svg3dBundle, Svg3dStandardMaterial};
+    pub use crate::slice::SvgSlices;
+    #[cfg(feature = "2d")]
+    pub use crate::sprite::SvgSprite;
+    pub use crate::svg::{
+        StrokeScaling, Svg, SvgAntiAliasing, SvgCurves, SvgSides, SvgVertexAttributes,
+    };
+    #[cfg(feature = "ui")]
+    pub use crate::ui::SvgNode;
     pub use lyon_tessellation::{
         FillOptions, FillRule, LineCap, LineJoin, Orientation, StrokeOptions,
     };
@@ -55,7 +114,17 @@ pub use plugin::SvgSet;
 
 #[cfg(any(feature = "2d", feature = "3d"))]
 use crate::plugin::SvgRenderPlugin;
-use crate::{loader::SvgAssetLoader, svg::Svg};
+use crate::{
+    loader::SvgAssetLoader,
+    processed::{ProcessedSvgAssetLoader, SvgAssetProcessor, SvgAssetSaver},
+    svg::Svg,
+};
+#[cfg(feature = "ui")]
+use bevy::{
+    app::PostUpdate,
+    ecs::schedule::IntoSystemConfigs,
+    ui::{widget::update_image_content_size_system, UiSystem},
+};
 use bevy::{
     app::{App, Plugin},
     asset::AssetApp,
@@ -67,9 +136,24 @@ pub struct SvgPlugin;
 impl Plugin for SvgPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<Svg>()
-            .init_asset_loader::<SvgAssetLoader>();
+            .init_asset_loader::<SvgAssetLoader>()
+            .init_asset_loader::<ProcessedSvgAssetLoader>()
+            // Only has an effect if the `AssetPlugin` runs in processed mode
+            .register_asset_processor::<SvgAssetProcessor>(SvgAssetSaver.into())
+            .set_default_asset_processor::<SvgAssetProcessor>("svg")
+            .set_default_asset_processor::<SvgAssetProcessor>("svgz");
         #[cfg(any(feature = "2d", feature = "3d"))]
         app.add_plugins(SvgRenderPlugin);
+        #[cfg(feature = "ui")]
+        app.add_systems(
+            PostUpdate,
+            (
+                ui::update_svg_node_content_size
+                    .in_set(UiSystem::Prepare)
+                    .after(update_image_content_size_system),
+                ui::rasterize_svg_nodes.in_set(UiSystem::PostLayout),
+            ),
+        );
     }
 }
 
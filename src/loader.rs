@@ -1,24 +1,48 @@
+#[cfg(feature = "rasterize")]
+use bevy::math::UVec2;
 use bevy::{
     asset::{io::Reader, AssetLoader, LoadContext},
     log::debug,
     utils::ConditionalSendFuture,
 };
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::svg::Svg;
+use crate::svg::{StrokeScaling, Svg, SvgAntiAliasing, SvgCurves, SvgVertexAttributes};
 
 #[derive(Default)]
 pub struct SvgAssetLoader;
 
+/// Settings of the [`Svg`] loader, set them with
+/// [`AssetServer::load_with_settings`](bevy::asset::AssetServer::load_with_settings).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SvgLoaderSettings {
+    /// Distance along the z-axis between consecutive paths, see [`Svg::depth_offset`].
+    pub depth_offset: f32,
+    /// How the width of the strokes changes when the SVG is scaled, see [`Svg::stroke_scaling`].
+    pub stroke_scaling: StrokeScaling,
+    /// How the edges are anti-aliased, see [`Svg::anti_aliasing`].
+    pub anti_aliasing: SvgAntiAliasing,
+    /// How the curves of the fills are rendered, see [`Svg::curves`].
+    pub curves: SvgCurves,
+    /// Which vertex attributes the mesh gets, see [`Svg::vertex_attributes`].
+    pub vertex_attributes: SvgVertexAttributes,
+    /// Size in pixels of an image the SVG is rasterized into, see [`Svg::rasterize`]. The image is
+    /// added as the labeled sub-asset `image`, e.g. load it with `"file.svg#image"`. The asset
+    /// processor stores the image together with the processed SVG.
+    #[cfg(feature = "rasterize")]
+    pub image_size: Option<UVec2>,
+}
+
 impl AssetLoader for SvgAssetLoader {
     type Asset = Svg;
-    type Settings = ();
+    type Settings = SvgLoaderSettings;
     type Error = FileSvgError;
 
     fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &(),
+        settings: &SvgLoaderSettings,
         load_context: &mut LoadContext<'_>,
     ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
         Box::pin(async move {
@@ -42,6 +66,11 @@ impl AssetLoader for SvgAssetLoader {
                 })?
                 .to_string_lossy();
             svg.name = name.to_string();
+            svg.depth_offset = settings.depth_offset;
+            svg.stroke_scaling = settings.stroke_scaling;
+            svg.anti_aliasing = settings.anti_aliasing;
+            svg.curves = settings.curves;
+            svg.vertex_attributes = settings.vertex_attributes;
             debug!("Parsing SVG: {} ... Done", load_context.path().display());
 
             debug!("Tessellating SVG: {} ...", load_context.path().display());
@@ -52,6 +81,13 @@ impl AssetLoader for SvgAssetLoader {
             );
             let mesh_handle = load_context.add_labeled_asset("mesh".to_string(), mesh);
             svg.mesh = mesh_handle;
+            svg.tessellated_from = Some(svg.tessellation_hash());
+
+            #[cfg(feature = "rasterize")]
+            if let Some(size) = settings.image_size {
+                let image = svg.rasterize(size.x, size.y);
+                load_context.add_labeled_asset("image".to_string(), image);
+            }
 
             Ok(svg)
         })
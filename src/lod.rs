@@ -0,0 +1,196 @@
+//! Levels of detail, which retessellate SVGs depending on their size on screen.
+
+use bevy::{
+    asset::{AssetEvent, AssetId, Assets, Handle},
+    ecs::{
+        component::Component,
+        event::EventReader,
+        system::{Query, Res, ResMut, Resource},
+    },
+    log::debug,
+    math::{Vec3, Vec3A},
+    render::{camera::Camera, mesh::Mesh, view::ViewVisibility},
+    transform::components::GlobalTransform,
+    utils::{HashMap, HashSet},
+};
+
+#[cfg(feature = "2d")]
+use bevy::ecs::query::Without;
+
+#[cfg(feature = "2d")]
+use crate::{fit::SvgFit, slice::SvgSlices};
+use crate::{
+    render::{self, SvgComponents, SvgMeshes},
+    retessellation::SvgTessellationTasks,
+    svg::Svg,
+};
+
+/// Retessellates an `Svg2d` or `Svg3d` depending on its size on screen, so curves stay
+/// smooth when zooming in, and no triangles are wasted when zooming out.
+///
+/// The tessellation tolerance is chosen so that the tessellated curves are at most
+/// [`tolerance`](SvgLod::tolerance) logical pixels away from the real ones, measured for the
+/// active camera with the highest order. To not retessellate on every frame while zooming, the
+/// tolerance is rounded down to a power of [`step`](SvgLod::step). The meshes of these levels
+/// are shared between all entities showing the same SVG.
+///
+/// It is ignored on an `Svg2d` with an `SvgFit` or `SvgSlices`, which replace the mesh as well.
+#[derive(Clone, Copy, Component, Debug, PartialEq)]
+pub struct SvgLod {
+    /// Maximum distance, in logical pixels, between the tessellated and the real curves.
+    pub tolerance: f32,
+    /// Ratio between the tolerances of two consecutive levels of detail, needs to be greater
+    /// than 1, smaller values are raised to just above 1. Larger steps retessellate less often,
+    /// but with finer meshes than necessary.
+    pub step: f32,
+    /// Lower limit of the tolerance, in SVG units, which keeps deep zooms from producing
+    /// meshes with an excessive number of triangles. Needs to be positive, smaller values are
+    /// raised to [`f32::EPSILON`].
+    pub min_tolerance: f32,
+}
+
+impl Default for SvgLod {
+    fn default() -> Self {
+        Self {
+            tolerance: 0.25,
+            step: 2.0,
+            min_tolerance: 0.0001,
+        }
+    }
+}
+
+impl SvgLod {
+    /// Level of detail for the given number of logical pixels per SVG unit, and its tolerance
+    /// in SVG units.
+    fn level(&self, pixels_per_unit: f32) -> (i32, f32) {
+        let step = self.step.max(1.0 + f32::EPSILON);
+        let min_level = self.min_tolerance.max(f32::EPSILON).log(step).ceil() as i32;
+        let level = (self.tolerance / pixels_per_unit)
+            .log(step)
+            .floor()
+            .max(min_level as f32) as i32;
+        (level, step.powi(level))
+    }
+}
+
+/// The meshes of all levels of detail in use, by [`Svg`] and level.
+#[derive(Debug, Default, Resource)]
+pub struct SvgLodMeshes(HashMap<(AssetId<Svg>, i32), Handle<Mesh>>);
+
+type SvgLodComponents = (
+    &'static SvgLod,
+    SvgComponents,
+    SvgMeshes,
+    &'static GlobalTransform,
+    &'static ViewVisibility,
+);
+
+/// `SvgFit` and `SvgSlices` replace the mesh with their own copy, which takes precedence.
+#[cfg(feature = "2d")]
+type SvgLodFilter = (Without<SvgFit>, Without<SvgSlices>);
+#[cfg(not(feature = "2d"))]
+type SvgLodFilter = ();
+
+/// Gives every visible [`SvgLod`] entity the mesh of its level of detail, and tessellates
+/// the levels that are missing in the background.
+///
+/// Levels that are neither in use nor next to a level in use are dropped, and all levels of
+/// an [`Svg`] are dropped when it is modified or removed.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_lod(
+    mut svg_events: EventReader<AssetEvent<Svg>>,
+    svgs: Res<Assets<Svg>>,
+    meshes: Res<Assets<Mesh>>,
+    mut lod_meshes: ResMut<SvgLodMeshes>,
+    mut tasks: ResMut<SvgTessellationTasks>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut query: Query<SvgLodComponents, SvgLodFilter>,
+) {
+    for event in svg_events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            lod_meshes.0.retain(|(svg_id, _), _| svg_id != id);
+        }
+    }
+
+    let Some((camera, camera_transform)) = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .max_by_key(|(camera, _)| camera.order)
+    else {
+        return;
+    };
+    let clip_from_view = camera.clip_from_view();
+    let is_orthographic = clip_from_view.w_axis.w == 1.0;
+    let viewport_height = camera
+        .logical_viewport_size()
+        .map_or(1.0, |size| size.y.max(1.0));
+    // Logical pixels per world unit, at a distance of one unit in front of a perspective camera
+    let pixels_per_world_unit = clip_from_view.y_axis.y * viewport_height / 2.0;
+
+    let mut used_levels = HashSet::new();
+    for (lod, svg, svg_meshes, transform, visibility) in &mut query {
+        let Some(handle) = render::svg_handle(svg) else {
+            continue;
+        };
+        let Some(svg) = svgs.get(handle) else {
+            continue;
+        };
+        if !visibility.get() {
+            continue;
+        }
+
+        let affine = transform.affine();
+        let world_units_per_unit = affine
+            .matrix3
+            .x_axis
+            .length()
+            .max(affine.matrix3.y_axis.length());
+        let distance = if is_orthographic {
+            1.0
+        } else {
+            // The closest corner of the SVG needs the finest tessellation
+            let corners = [
+                Vec3::ZERO,
+                Vec3::new(svg.size.x, 0.0, 0.0),
+                Vec3::new(0.0, -svg.size.y, 0.0),
+                Vec3::new(svg.size.x, -svg.size.y, 0.0),
+            ];
+            let camera_position = camera_transform.translation_vec3a();
+            let forward = Vec3A::from(*camera_transform.forward());
+            corners
+                .into_iter()
+                .map(|corner| {
+                    (affine.transform_point3a(corner.into()) - camera_position).dot(forward)
+                })
+                .fold(f32::INFINITY, f32::min)
+                .max(0.001)
+        };
+        let pixels_per_unit = pixels_per_world_unit * world_units_per_unit / distance;
+        if !pixels_per_unit.is_finite() || pixels_per_unit <= 0.0 {
+            continue;
+        }
+
+        let (level, tolerance) = lod.level(pixels_per_unit);
+        used_levels.insert((handle.id(), level));
+        let lod_mesh = lod_meshes.0.entry((handle.id(), level)).or_insert_with(|| {
+            debug!(
+                "Tessellating SVG `{}` with a tolerance of {tolerance}.",
+                svg.name
+            );
+            let lod_mesh = meshes.reserve_handle();
+            tasks.spawn(lod_mesh.clone(), svg.clone(), Some(tolerance));
+            lod_mesh
+        });
+        // The previous mesh stays until the level is tessellated in the background
+        if !meshes.contains(&*lod_mesh) {
+            continue;
+        }
+
+        render::replace_svg_mesh(svg_meshes, lod_mesh);
+    }
+
+    // Neighbouring levels are kept, so zooming back and forth doesn't retessellate
+    lod_meshes.0.retain(|(id, level), _| {
+        (level - 1..=level + 1).any(|level| used_levels.contains(&(*id, level)))
+    });
+}
@@ -0,0 +1,136 @@
+//! Copies of the mesh of an `Svg2d` with moved vertices, which fit or stretch the SVG.
+
+use std::{fmt::Debug, marker::PhantomData};
+
+use bevy::{
+    asset::{AssetEvent, AssetId, Assets, Handle},
+    ecs::{
+        change_detection::{DetectChanges, Ref},
+        component::Component,
+        entity::Entity,
+        event::EventReader,
+        query::{Added, Has, Or, QueryFilter, With},
+        system::{Commands, Query, Res, ResMut},
+    },
+    log::{debug, warn},
+    math::Vec2,
+    render::{
+        mesh::{Mesh, Mesh2d},
+        primitives::Aabb,
+    },
+    utils::HashSet,
+};
+
+use crate::{
+    fit::SvgFit,
+    lod::SvgLod,
+    render::{vertex_buffer::map_svg_mesh, Svg2d},
+    slice::SvgSlices,
+    svg::Svg,
+};
+
+/// A component that moves the vertices of the mesh of an `Svg2d`, which then shows its own copy
+/// of the mesh, kept in [`SvgMappedMesh`].
+pub(crate) trait SvgMeshMapping: Component + Debug {
+    /// Filter that skips the entities with a mapping that takes precedence over this one.
+    type Filter: QueryFilter;
+
+    /// Maps a point of the SVG, in SVG coordinates, to its position in the copied mesh.
+    fn map_mesh_point(&self, point: Vec2, svg: &Svg) -> Vec2;
+}
+
+/// The copy of the mesh of an `Svg2d`, with its vertices moved by the component `M`.
+#[derive(Component, Debug)]
+pub struct SvgMappedMesh<M: Send + Sync + 'static>(Handle<Mesh>, PhantomData<fn() -> M>);
+
+impl<M: Send + Sync + 'static> Default for SvgMappedMesh<M> {
+    fn default() -> Self {
+        Self(Handle::default(), PhantomData)
+    }
+}
+
+/// The [`Svg`]s that were loaded or modified since the events were last read.
+pub(crate) fn modified_svgs(
+    svg_events: &mut EventReader<AssetEvent<Svg>>,
+) -> HashSet<AssetId<Svg>> {
+    svg_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Gives every `Svg2d` with the component `M` its own copy of the mesh of its SVG, with the
+/// vertices moved by `M`, whenever `M`, the SVG or its mesh change. The mesh of an SVG changes
+/// when it is retessellated in the background.
+pub fn apply_mesh_mapping<M: SvgMeshMapping>(
+    mut commands: Commands,
+    mut svg_events: EventReader<AssetEvent<Svg>>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    svgs: Res<Assets<Svg>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut query: Query<(Entity, &Svg2d, Ref<M>, &mut Mesh2d, &mut SvgMappedMesh<M>), M::Filter>,
+) {
+    let modified = modified_svgs(&mut svg_events);
+    let modified_meshes: HashSet<AssetId<Mesh>> = mesh_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, svg_2d, mapping, mut mesh_2d, mut mapped_mesh) in &mut query {
+        let Some(svg) = svgs.get(&svg_2d.0) else {
+            continue;
+        };
+        // The mesh is replaced by the mesh of the SVG whenever the SVG or its handle changes
+        if !(mapping.is_changed()
+            || modified.contains(&svg_2d.0.id())
+            || modified_meshes.contains(&svg.mesh.id())
+            || mesh_2d.0 != mapped_mesh.0)
+        {
+            continue;
+        }
+        debug!(
+            "Mapping the mesh of SVG `{}` with {:?}.",
+            svg.name, *mapping
+        );
+        if !map_svg_mesh(&mut meshes, svg, &mut mapped_mesh.0, |point| {
+            mapping.map_mesh_point(point, svg)
+        }) {
+            continue;
+        }
+        if mesh_2d.0 != mapped_mesh.0 {
+            mesh_2d.0 = mapped_mesh.0.clone();
+        }
+        // The bounding box is only computed for meshes without one
+        commands.entity(entity).remove::<Aabb>();
+    }
+}
+
+/// Warns about every `Svg2d` that gets more than one of [`SvgSlices`], [`SvgFit`] and [`SvgLod`],
+/// which all replace its mesh. Only the first of them is applied, in this order.
+pub fn warn_combined_mappings(
+    query: Query<
+        (Entity, Has<SvgSlices>, Has<SvgFit>, Has<SvgLod>),
+        (
+            With<Svg2d>,
+            Or<(Added<SvgSlices>, Added<SvgFit>, Added<SvgLod>)>,
+        ),
+    >,
+) {
+    for (entity, has_slices, has_fit, has_lod) in &query {
+        let ignored = match (has_slices, has_fit, has_lod) {
+            (true, true, true) => "SvgFit and SvgLod are",
+            (true, true, false) => "SvgFit is",
+            (true, false, true) | (false, true, true) => "SvgLod is",
+            _ => continue,
+        };
+        warn!(
+            "Entity {entity} has more than one of SvgSlices, SvgFit and SvgLod, which all replace its mesh. {ignored} ignored."
+        );
+    }
+}
@@ -0,0 +1,655 @@
+//! Multi-channel signed distance fields of the fills of SVGs, which stay crisp at any zoom.
+//!
+//! The field is generated the way `msdfgen` does it: the edges of every contour are colored so
+//! that two channels meet at corners, every channel stores the signed pseudo-distance to the
+//! closest edge of its color, and the median of the three channels reconstructs sharp corners.
+
+use bevy::{
+    image::{Image, ImageSampler},
+    log::debug,
+    math::Vec2,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use lyon_path::{
+    geom::{CubicBezierSegment, QuadraticBezierSegment},
+    math::{Point, Transform},
+    PathEvent,
+};
+
+use crate::{
+    svg::{DrawType, PathDescriptor, Svg},
+    util::geometry::is_inside,
+};
+
+/// Tolerance in pixels of the field used to flatten curves into line segments.
+const TOLERANCE: f32 = 0.05;
+/// Sine of the angle between two edges above which they meet in a corner, the same as `msdfgen`.
+const CORNER_THRESHOLD: f32 = 0.141_12;
+
+// Colors of the edges, as bit masks of the channels they are stored in
+const BLACK: u8 = 0;
+const RED: u8 = 1;
+const GREEN: u8 = 2;
+const BLUE: u8 = 4;
+const CYAN: u8 = 6;
+const WHITE: u8 = 7;
+
+impl Svg {
+    /// Generates a multi-channel signed distance field (MSDF) of the fills of the SVG, with the
+    /// given size in pixels, to be rendered with an
+    /// [`SvgMsdfMaterial`](crate::prelude::SvgMsdfMaterial).
+    ///
+    /// The SVG is stretched to fill the whole image, like [`Svg::rasterize`]. The red, green and
+    /// blue channels store the multi-channel distance, whose median is the signed distance to the
+    /// outline, and the alpha channel stores the true signed distance. Distances are positive
+    /// inside of the fills and encoded as `distance / distance_range + 0.5`, so `distance_range`
+    /// is the width in pixels of the band around the outline in which the field isn't clamped.
+    /// A range of 4 to 8 pixels works well, larger ranges allow effects like outlines.
+    ///
+    /// Only the shape of the fills is kept, strokes and colors are ignored. Generating the field
+    /// is expensive, so it is best done once and the image reused, a small field of 64 by 64
+    /// pixels is often enough for an icon.
+    pub fn generate_msdf(&self, width: u32, height: u32, distance_range: f32) -> Image {
+        debug!(
+            "Generating MSDF of SVG `{}` at {width}x{height} ...",
+            self.name
+        );
+        let (width, height) = (width.max(1), height.max(1));
+        let scale =
+            Vec2::new(width as f32, height as f32) / self.size.max(Vec2::splat(f32::EPSILON));
+        let shape = Shape::new(
+            self.paths
+                .iter()
+                .filter(|path| matches!(path.draw_type, DrawType::Fill)),
+            scale,
+        );
+        let distance_range = distance_range.max(f32::EPSILON);
+
+        let mut selectors = Vec::with_capacity(shape.contours.len());
+        let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let point = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let distance = shape.distance(point, &mut selectors);
+                data.extend(distance.map(|distance| {
+                    ((distance / distance_range + 0.5).clamp(0.0, 1.0) * 255.0).round() as u8
+                }));
+            }
+        }
+        debug!("Generating MSDF of SVG `{}` ... Done", self.name);
+
+        let mut image = Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            // Distances are linear, and have to be interpolated linearly
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::default(),
+        );
+        image.sampler = ImageSampler::linear();
+        image
+    }
+}
+
+/// All contours of the fills, in pixel coordinates of the field.
+struct Shape {
+    contours: Vec<Contour>,
+    /// The flattened contours of every path, to check if a point is filled.
+    polygons: Vec<Vec<Vec<Vec2>>>,
+}
+
+/// A closed contour, oriented so that its filled side is on the left of its edges.
+struct Contour {
+    edges: Vec<Edge>,
+    /// `1.0` for contours around a filled area, `-1.0` for holes.
+    winding: f32,
+}
+
+/// A segment of a path, flattened into a polyline.
+#[derive(Clone)]
+struct Edge {
+    points: Vec<Vec2>,
+    color: u8,
+}
+
+/// Distance from a point to an edge.
+#[derive(Clone, Copy)]
+struct EdgeDistance {
+    /// The true signed distance.
+    distance: f32,
+    /// How parallel the point is to the edge, when its closest point is an end of the edge,
+    /// which decides between edges that are equally close.
+    dot: f32,
+    /// The signed distance to the edge, extended beyond its ends along their direction.
+    pseudo_distance: f32,
+}
+
+/// The closest edge of every channel, and the closest edge of any color.
+#[derive(Clone, Copy)]
+struct Selector {
+    channels: [EdgeDistance; 3],
+    closest: EdgeDistance,
+}
+
+impl Shape {
+    fn new<'a>(paths: impl Iterator<Item = &'a PathDescriptor>, scale: Vec2) -> Self {
+        let mut contours = Vec::new();
+        let mut polygons = Vec::new();
+        let mut color = CYAN;
+        for path in paths {
+            let mut path_contours = path_contours(path, scale);
+            let polygon = path_contours
+                .iter()
+                .map(|edges| outline(edges))
+                .collect::<Vec<_>>();
+            for (edges, points) in path_contours.iter_mut().zip(&polygon) {
+                let area = signed_area(points);
+                if area.abs() <= f32::EPSILON {
+                    continue;
+                }
+                // The filled side is found with the even-odd rule the path is tessellated with
+                let winding = if is_filled_on_left(points, &polygon) {
+                    area.signum()
+                } else {
+                    edges.reverse();
+                    for edge in edges.iter_mut() {
+                        edge.points.reverse();
+                    }
+                    -area.signum()
+                };
+                color_edges(edges, &mut color);
+                contours.push(Contour {
+                    edges: std::mem::take(edges),
+                    winding,
+                });
+            }
+            polygons.push(polygon);
+        }
+        Self { contours, polygons }
+    }
+
+    /// The multi-channel and true distance of the point, combined over all contours so that
+    /// overlapping contours form their union, the same way as `OverlappingContourCombiner` of
+    /// `msdfgen`.
+    fn distance(&self, point: Vec2, selectors: &mut Vec<[f32; 4]>) -> [f32; 4] {
+        selectors.clear();
+        let mut shape = Selector::EMPTY;
+        let mut inner = Selector::EMPTY;
+        let mut outer = Selector::EMPTY;
+        for contour in &self.contours {
+            let mut selector = Selector::EMPTY;
+            for edge in &contour.edges {
+                selector.add(edge.distance(point), edge.color);
+            }
+            let distance = selector.distance();
+            shape.merge(&selector);
+            if contour.winding > 0.0 && median(distance) >= 0.0 {
+                inner.merge(&selector);
+            }
+            if contour.winding < 0.0 && median(distance) <= 0.0 {
+                outer.merge(&selector);
+            }
+            selectors.push(distance);
+        }
+
+        let shape = shape.distance();
+        let (inner, outer) = (inner.distance(), outer.distance());
+        let (inner_median, outer_median) = (median(inner), median(outer));
+        let contours = self.contours.iter().zip(selectors.iter());
+        let (mut distance, winding) = if inner_median >= 0.0
+            && inner_median.abs() <= outer_median.abs()
+        {
+            let mut distance = inner;
+            for (_, &contour_distance) in contours.clone().filter(|(c, _)| c.winding > 0.0) {
+                let contour_median = median(contour_distance);
+                if contour_median.abs() < outer_median.abs() && contour_median > median(distance) {
+                    distance = contour_distance;
+                }
+            }
+            (distance, 1.0)
+        } else if outer_median <= 0.0 && outer_median.abs() < inner_median.abs() {
+            let mut distance = outer;
+            for (_, &contour_distance) in contours.clone().filter(|(c, _)| c.winding < 0.0) {
+                let contour_median = median(contour_distance);
+                if contour_median.abs() < inner_median.abs() && contour_median < median(distance) {
+                    distance = contour_distance;
+                }
+            }
+            (distance, -1.0)
+        } else {
+            (shape, 0.0)
+        };
+        if winding != 0.0 {
+            for (_, &contour_distance) in contours.filter(|(c, _)| c.winding != winding) {
+                let contour_median = median(contour_distance);
+                if contour_median * median(distance) >= 0.0
+                    && contour_median.abs() < median(distance).abs()
+                {
+                    distance = contour_distance;
+                }
+            }
+            if median(distance) == median(shape) {
+                distance = shape;
+            }
+        }
+
+        // Where the channels disagree with the fill, the true distance is used for all of them
+        let inside = self
+            .polygons
+            .iter()
+            .any(|polygon| is_inside(point, polygon));
+        let true_distance = if inside {
+            distance[3].abs()
+        } else {
+            -distance[3].abs()
+        };
+        if (median(distance) > 0.0) != inside {
+            [true_distance; 4]
+        } else {
+            distance[3] = true_distance;
+            distance
+        }
+    }
+}
+
+impl Edge {
+    fn distance(&self, point: Vec2) -> EdgeDistance {
+        let mut closest = EdgeDistance::NONE;
+        let mut closest_piece = (0, 0.0);
+        for (index, piece) in self.points.windows(2).enumerate() {
+            let (start, direction) = (piece[0], piece[1] - piece[0]);
+            let t = (point - start).dot(direction) / direction.length_squared();
+            let clamped = t.clamp(0.0, 1.0);
+            let offset = point - (start + direction * clamped);
+            let dot = if clamped == t {
+                0.0
+            } else {
+                direction.normalize().dot(offset.normalize_or_zero()).abs()
+            };
+            let sign = if direction.perp_dot(point - start) >= 0.0 {
+                1.0
+            } else {
+                -1.0
+            };
+            let distance = offset.length() * sign;
+            let candidate = EdgeDistance {
+                distance,
+                dot,
+                pseudo_distance: distance,
+            };
+            if candidate.is_closer_than(&closest) {
+                closest = candidate;
+                closest_piece = (index, t);
+            }
+        }
+
+        // Beyond the ends, the distance to the line through the first or last piece is used
+        let (index, t) = closest_piece;
+        let last = self.points.len() - 2;
+        let end = if index == 0 && t < 0.0 {
+            Some((self.points[0], self.points[1] - self.points[0]))
+        } else if index == last && t > 1.0 {
+            Some((
+                self.points[last + 1],
+                self.points[last + 1] - self.points[last],
+            ))
+        } else {
+            None
+        };
+        if let Some((end, direction)) = end {
+            let direction = direction.normalize();
+            let offset = point - end;
+            if offset.dot(direction) * (t - 0.5) > 0.0 {
+                let pseudo_distance = direction.perp_dot(offset);
+                if pseudo_distance.abs() <= closest.distance.abs() {
+                    closest.pseudo_distance = pseudo_distance;
+                }
+            }
+        }
+        closest
+    }
+
+    /// Direction at the start of the edge.
+    fn start_direction(&self) -> Vec2 {
+        (self.points[1] - self.points[0]).normalize()
+    }
+
+    /// Direction at the end of the edge.
+    fn end_direction(&self) -> Vec2 {
+        let count = self.points.len();
+        (self.points[count - 1] - self.points[count - 2]).normalize()
+    }
+
+    /// Splits the edge into two halves.
+    fn split(&self) -> (Self, Self) {
+        let mut points = self.points.clone();
+        if points.len() == 2 {
+            points.insert(1, points[0].midpoint(points[1]));
+        }
+        let middle = points.len() / 2;
+        (
+            Self {
+                points: points[..=middle].to_vec(),
+                color: self.color,
+            },
+            Self {
+                points: points[middle..].to_vec(),
+                color: self.color,
+            },
+        )
+    }
+}
+
+impl EdgeDistance {
+    /// An edge infinitely far outside, for channels without edges.
+    const NONE: Self = Self {
+        distance: -f32::MAX,
+        dot: 1.0,
+        pseudo_distance: -f32::MAX,
+    };
+
+    fn is_closer_than(&self, other: &Self) -> bool {
+        let (distance, other_distance) = (self.distance.abs(), other.distance.abs());
+        distance < other_distance || (distance == other_distance && self.dot < other.dot)
+    }
+}
+
+impl Selector {
+    const EMPTY: Self = Self {
+        channels: [EdgeDistance::NONE; 3],
+        closest: EdgeDistance::NONE,
+    };
+
+    fn add(&mut self, distance: EdgeDistance, color: u8) {
+        for (channel, mask) in self.channels.iter_mut().zip([RED, GREEN, BLUE]) {
+            if color & mask != 0 && distance.is_closer_than(channel) {
+                *channel = distance;
+            }
+        }
+        if distance.is_closer_than(&self.closest) {
+            self.closest = distance;
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        for (channel, other) in self.channels.iter_mut().zip(&other.channels) {
+            if other.is_closer_than(channel) {
+                *channel = *other;
+            }
+        }
+        if other.closest.is_closer_than(&self.closest) {
+            self.closest = other.closest;
+        }
+    }
+
+    /// The pseudo-distances of the channels, followed by the true distance.
+    fn distance(&self) -> [f32; 4] {
+        let [red, green, blue] = self.channels.map(|channel| channel.pseudo_distance);
+        [red, green, blue, self.closest.distance]
+    }
+}
+
+/// Median of the three color channels.
+fn median(distance: [f32; 4]) -> f32 {
+    let [a, b, c, _] = distance;
+    a.min(b).max(a.max(b).min(c))
+}
+
+/// Splits the fill of a path into closed contours of edges, in pixel coordinates.
+fn path_contours(path: &PathDescriptor, scale: Vec2) -> Vec<Vec<Edge>> {
+    let t = path.abs_transform;
+    let transform = Transform::new(t.sx, t.ky, t.kx, t.sy, t.tx, t.ty).then_scale(scale.x, scale.y);
+    let vec2 = |point: Point| Vec2::new(point.x, point.y);
+    let push = |edges: &mut Vec<Edge>, points: &[Vec2]| {
+        let mut edge = Edge {
+            points: Vec::with_capacity(points.len()),
+            color: WHITE,
+        };
+        for &point in points {
+            if edge
+                .points
+                .last()
+                .map_or(true, |last| last.distance_squared(point) > f32::EPSILON)
+            {
+                edge.points.push(point);
+            }
+        }
+        if edge.points.len() > 1 {
+            edges.push(edge);
+        }
+    };
+
+    let mut contours = Vec::new();
+    let mut edges = Vec::new();
+    let mut points = Vec::new();
+    let (mut first, mut last) = (Point::zero(), Point::zero());
+    for event in &path.segments {
+        points.clear();
+        match event.transformed(&transform) {
+            PathEvent::Begin { at } => {
+                edges.clear();
+                (first, last) = (at, at);
+            }
+            PathEvent::Line { to, .. } => {
+                push(&mut edges, &[vec2(last), vec2(to)]);
+                last = to;
+            }
+            PathEvent::Quadratic { ctrl, to, .. } => {
+                points.push(vec2(last));
+                QuadraticBezierSegment {
+                    from: last,
+                    ctrl,
+                    to,
+                }
+                .for_each_flattened(TOLERANCE, &mut |line| points.push(vec2(line.to)));
+                push(&mut edges, &points);
+                last = to;
+            }
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                points.push(vec2(last));
+                CubicBezierSegment {
+                    from: last,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                }
+                .for_each_flattened(TOLERANCE, &mut |line| points.push(vec2(line.to)));
+                push(&mut edges, &points);
+                last = to;
+            }
+            // Fills are always closed. Only the end points of the events are relied on, as
+            // they are the only ones the tessellators use
+            PathEvent::End { .. } => {
+                push(&mut edges, &[vec2(last), vec2(first)]);
+                if !edges.is_empty() {
+                    contours.push(std::mem::take(&mut edges));
+                }
+            }
+        }
+    }
+    contours
+}
+
+/// The points of the closed outline of a contour, without repeating the first one.
+fn outline(edges: &[Edge]) -> Vec<Vec2> {
+    edges
+        .iter()
+        .flat_map(|edge| &edge.points[..edge.points.len() - 1])
+        .copied()
+        .collect()
+}
+
+/// Twice the signed area of a closed outline, positive if it turns to the left.
+fn signed_area(points: &[Vec2]) -> f32 {
+    let mut prev = points[points.len() - 1];
+    let mut area = 0.0;
+    for &curr in points {
+        area += prev.perp_dot(curr);
+        prev = curr;
+    }
+    area
+}
+
+/// Checks if the area on the left of the longest piece of the outline is filled.
+fn is_filled_on_left(points: &[Vec2], polygon: &[Vec<Vec2>]) -> bool {
+    let mut prev = points[points.len() - 1];
+    let mut longest = (Vec2::ZERO, Vec2::ZERO);
+    for &curr in points {
+        if prev.distance_squared(curr) > longest.0.distance_squared(longest.1) {
+            longest = (prev, curr);
+        }
+        prev = curr;
+    }
+    let (start, end) = longest;
+    let direction = end - start;
+    let offset = direction.perp() / direction.length() * (direction.length() * 0.01).min(0.01);
+    is_inside(start.midpoint(end) + offset, polygon)
+}
+
+/// Colors the edges of a contour so that the edges meeting in a corner share exactly one
+/// channel, following the simple edge coloring of `msdfgen`. The color of the previous contour
+/// is continued.
+fn color_edges(edges: &mut Vec<Edge>, color: &mut u8) {
+    let count = edges.len();
+    let corners = (0..count)
+        .filter(|&index| {
+            let incoming = edges[(index + count - 1) % count].end_direction();
+            let outgoing = edges[index].start_direction();
+            incoming.dot(outgoing) <= 0.0 || incoming.perp_dot(outgoing).abs() > CORNER_THRESHOLD
+        })
+        .collect::<Vec<_>>();
+
+    match corners[..] {
+        // A smooth contour
+        [] => {
+            switch_color(color, BLACK);
+            for edge in edges.iter_mut() {
+                edge.color = *color;
+            }
+        }
+        // A teardrop, split into three parts with two colors meeting in the corner
+        [corner] => {
+            switch_color(color, BLACK);
+            let first = *color;
+            switch_color(color, BLACK);
+            let colors = [first, WHITE, *color];
+            edges.rotate_left(corner);
+            while edges.len() < 3 {
+                let longest = (0..edges.len())
+                    .max_by_key(|&index| edges[index].points.len())
+                    .unwrap_or_default();
+                let (start, end) = edges[longest].split();
+                edges[longest] = start;
+                edges.insert(longest + 1, end);
+            }
+            let count = edges.len();
+            for (index, edge) in edges.iter_mut().enumerate() {
+                let position = 2.875 * index as f32 / (count - 1) as f32;
+                let part = (3.0 + position - 1.4375 + 0.5) as usize - 2;
+                edge.color = colors[part];
+            }
+        }
+        // Every spline between two corners gets its own color
+        _ => {
+            let start = corners[0];
+            let mut spline = 0;
+            switch_color(color, BLACK);
+            let initial = *color;
+            for offset in 0..count {
+                let index = (start + offset) % count;
+                if spline + 1 < corners.len() && corners[spline + 1] == index {
+                    spline += 1;
+                    let banned = if spline == corners.len() - 1 {
+                        initial
+                    } else {
+                        BLACK
+                    };
+                    switch_color(color, banned);
+                }
+                edges[index].color = *color;
+            }
+        }
+    }
+}
+
+/// Switches to the next color with two channels, avoiding the `banned` one.
+fn switch_color(color: &mut u8, banned: u8) {
+    let combined = *color & banned;
+    if matches!(combined, RED | GREEN | BLUE) {
+        *color = combined ^ WHITE;
+    } else if *color == BLACK || *color == WHITE {
+        *color = CYAN;
+    } else {
+        let shifted = *color << 1;
+        *color = (shifted | shifted >> 3) & WHITE;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A square from 2.5 to 7.5 on a field of 10 by 10 pixels, whose left edge goes through the
+    /// centers of the pixels in the third column.
+    const SQUARE: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+        <path d="M 2.5 2.5 L 7.5 2.5 L 7.5 7.5 L 2.5 7.5 Z"/>
+    </svg>"#;
+
+    /// Median and true distance of the pixel, decoded back into pixels.
+    fn distance_at(image: &Image, x: usize, y: usize, distance_range: f32) -> (f32, f32) {
+        let index = (y * image.width() as usize + x) * 4;
+        let distance = [0, 1, 2, 3]
+            .map(|channel| (image.data[index + channel] as f32 / 255.0 - 0.5) * distance_range);
+        (median(distance), distance[3])
+    }
+
+    #[test]
+    fn square() {
+        let svg: Svg = SQUARE.parse().unwrap();
+        let image = svg.generate_msdf(10, 10, 4.0);
+        assert_eq!(image.data.len(), 10 * 10 * 4);
+        // One step of the encoding
+        let epsilon = 4.0 / 255.0;
+
+        for (x, y, expected) in [
+            // Inside, in the middle and next to the left edge
+            (5, 5, 2.0),
+            (3, 5, 1.0),
+            // On the left edge
+            (2, 5, 0.0),
+            // Outside, next to the left edge and beyond the range
+            (1, 5, -1.0),
+            (0, 0, -2.0),
+        ] {
+            let (median, distance) = distance_at(&image, x, y, 4.0);
+            assert!(
+                (median - expected).abs() <= epsilon,
+                "median {median} at ({x}, {y}), expected {expected}"
+            );
+            assert!(
+                (distance - expected).abs() <= epsilon,
+                "distance {distance} at ({x}, {y}), expected {expected}"
+            );
+        }
+
+        // The corner stays sharp in the median, while the true distance is rounded
+        let (median, distance) = distance_at(&image, 1, 1, 4.0);
+        assert!(
+            (median + 1.0).abs() <= epsilon,
+            "median {median} at the corner"
+        );
+        assert!(
+            distance < median - epsilon,
+            "distance {distance} at the corner"
+        );
+    }
+}
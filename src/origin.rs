@@ -1,5 +1,3 @@
-#[cfg(feature = "2d")]
-use bevy::render::mesh::Mesh2d;
 #[cfg(feature = "3d")]
 use bevy::render::mesh::Mesh3d;
 use bevy::{
@@ -11,12 +9,20 @@ use bevy::{
         query::{Changed, Or, With, Without},
         system::{Commands, Query, Res},
     },
-    math::{Vec2, Vec3, Vec3Swizzles},
+    math::{Rect, Vec2, Vec3, Vec3Swizzles},
     transform::components::{GlobalTransform, Transform},
 };
+#[cfg(feature = "2d")]
+use bevy::{render::mesh::Mesh2d, sprite::Anchor};
 
+#[cfg(feature = "2d")]
+use crate::render::Svg2d;
+#[cfg(feature = "3d")]
+use crate::render::Svg3d;
 use crate::{
-    render::{Svg2d, Svg3d},
+    fit::SvgFit,
+    render::{self, SvgComponents},
+    slice::SvgSlices,
     svg::Svg,
 };
 
@@ -39,26 +45,75 @@ pub enum Origin {
 }
 
 impl Origin {
+    /// Position of the origin relative to its reference rectangle, top left is (0, 0),
+    /// bottom right is (1, 1).
+    pub fn relative_position(&self) -> Vec2 {
+        match self {
+            Origin::BottomLeft => Vec2::new(0.0, 1.0),
+            Origin::BottomRight => Vec2::new(1.0, 1.0),
+            Origin::Center => Vec2::new(0.5, 0.5),
+            Origin::TopLeft => Vec2::new(0.0, 0.0),
+            Origin::TopRight => Vec2::new(1.0, 0.0),
+            Origin::Custom(coord) => Vec2::new(coord.0, coord.1),
+        }
+    }
+
     /// Computes the translation for an origin. The resulting translation needs to be added
     /// to the translation of the SVG.
     pub fn compute_translation(&self, scaled_size: Vec2) -> Vec3 {
-        match self {
-            Origin::BottomLeft => Vec3::new(0.0, scaled_size.y, 0.0),
-            Origin::BottomRight => Vec3::new(-scaled_size.x, scaled_size.y, 0.0),
-            Origin::Center => Vec3::new(-scaled_size.x * 0.5, scaled_size.y * 0.5, 0.0),
-            // Standard SVG origin is top left, so we don't need to do anything
-            Origin::TopLeft => Vec3::ZERO,
-            Origin::TopRight => Vec3::new(-scaled_size.x, 0.0, 0.0),
-            Origin::Custom(coord) => {
-                Vec3::new(-scaled_size.x * coord.0, scaled_size.y * coord.1, 0.0)
+        self.compute_translation_in(Rect {
+            min: Vec2::ZERO,
+            max: scaled_size,
+        })
+    }
+
+    /// Computes the translation for an origin relative to the given, already scaled, rectangle
+    /// in SVG coordinates. The resulting translation needs to be added to the translation of the SVG.
+    pub fn compute_translation_in(&self, scaled_rect: Rect) -> Vec3 {
+        let anchor =
+            scaled_rect.min + (scaled_rect.max - scaled_rect.min) * self.relative_position();
+        // SVG coordinates have their y-axis pointing down, Bevy has it pointing up
+        Vec3::new(-anchor.x, anchor.y, 0.0)
+    }
+}
+
+/// Converts the anchor of a `Sprite` into the same origin. Anchors have their y-axis pointing
+/// up and are centered at (0, 0), origins are relative to the top left.
+#[cfg(feature = "2d")]
+impl From<Anchor> for Origin {
+    fn from(anchor: Anchor) -> Self {
+        match anchor {
+            Anchor::Center => Origin::Center,
+            Anchor::BottomLeft => Origin::BottomLeft,
+            Anchor::BottomRight => Origin::BottomRight,
+            Anchor::TopLeft => Origin::TopLeft,
+            Anchor::TopRight => Origin::TopRight,
+            anchor => {
+                let position = anchor.as_vec();
+                Origin::Custom((position.x + 0.5, 0.5 - position.y))
             }
         }
     }
 }
 
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq, Eq)]
+/// The rectangle an [`Origin`] is relative to.
+pub enum OriginBounds {
+    #[default]
+    /// The size of the SVG document, given by its `width` and `height`.
+    Size,
+    /// The `viewBox` of the SVG, mapped into the document.
+    ViewBox,
+    /// The tight bounding box of the content of the SVG.
+    Content,
+}
+
 #[derive(Clone, Component, Copy, Debug, PartialEq)]
 pub struct OriginState {
     previous: Origin,
+    previous_bounds: OriginBounds,
+    previous_slices: Option<SvgSlices>,
+    previous_fit: Option<SvgFit>,
 }
 
 #[cfg(feature = "2d")]
@@ -88,6 +143,9 @@ pub fn add_origin_state(
     for entity in &query {
         commands.entity(entity).insert(OriginState {
             previous: Origin::default(),
+            previous_bounds: OriginBounds::default(),
+            previous_slices: None,
+            previous_fit: None,
         });
     }
 }
@@ -108,37 +166,79 @@ pub fn apply_origin(
     mut query: Query<
         (
             Entity,
-            Option<&Svg2d>,
-            Option<&Svg3d>,
+            SvgComponents,
             &Origin,
+            &OriginBounds,
+            Option<&SvgSlices>,
+            Option<&SvgFit>,
             &mut OriginState,
             Ref<Transform>,
             &mut GlobalTransform,
         ),
-        Or<(Changed<Origin>, Changed<Transform>, ChangedMesh)>,
+        Or<(
+            Changed<Origin>,
+            Changed<OriginBounds>,
+            Changed<SvgSlices>,
+            Changed<SvgFit>,
+            Changed<Transform>,
+            ChangedMesh,
+        )>,
     >,
 ) {
     for (
         _,
-        svg2d_handle,
-        svg3d_handle,
+        svg,
         origin,
+        origin_bounds,
+        slices,
+        fit,
         mut origin_state,
         transform,
         mut global_transform,
     ) in &mut query
     {
-        let Some(svg_handle) =
-            svg2d_handle.map_or_else(|| svg3d_handle.map(|x| &x.0), |x| Some(&x.0))
-        else {
+        let Some(svg_handle) = render::svg_handle(svg) else {
             continue;
         };
         if let Some(svg) = svgs.get(svg_handle) {
-            if origin_state.previous != *origin {
-                let scaled_size = svg.size * transform.scale.xy();
+            let scale = transform.scale.xy();
+            let (slices, fit) = (slices.copied(), fit.copied());
+            // Sliced and fitted SVGs are moved within their mesh, and their bounds with them
+            let scaled_bounds = |bounds, slices: Option<SvgSlices>, fit: Option<SvgFit>| {
+                let mut rect = svg.bounds(bounds);
+                if let Some(slices) = slices {
+                    rect = Rect::from_corners(
+                        slices.map_point(rect.min, svg.size),
+                        slices.map_point(rect.max, svg.size),
+                    );
+                } else if let Some(fit) = fit {
+                    rect = if bounds == OriginBounds::Size {
+                        Rect::from_corners(Vec2::ZERO, fit.size)
+                    } else {
+                        Rect::from_corners(
+                            fit.map_point(rect.min, svg),
+                            fit.map_point(rect.max, svg),
+                        )
+                    };
+                }
+                Rect {
+                    min: rect.min * scale,
+                    max: rect.max * scale,
+                }
+            };
+            if origin_state.previous != *origin
+                || origin_state.previous_bounds != *origin_bounds
+                || origin_state.previous_slices != slices
+                || origin_state.previous_fit != fit
+            {
                 let reverse_origin_translation =
-                    origin_state.previous.compute_translation(scaled_size);
-                let origin_translation = origin.compute_translation(scaled_size);
+                    origin_state.previous.compute_translation_in(scaled_bounds(
+                        origin_state.previous_bounds,
+                        origin_state.previous_slices,
+                        origin_state.previous_fit,
+                    ));
+                let origin_translation =
+                    origin.compute_translation_in(scaled_bounds(*origin_bounds, slices, fit));
 
                 let mut gtransf = global_transform.compute_transform();
                 gtransf.translation.x += origin_translation.x - reverse_origin_translation.x;
@@ -147,9 +247,12 @@ pub fn apply_origin(
                 *global_transform = GlobalTransform::from(gtransf);
 
                 origin_state.previous = *origin;
+                origin_state.previous_bounds = *origin_bounds;
+                origin_state.previous_slices = slices;
+                origin_state.previous_fit = fit;
             } else if transform.is_changed() {
-                let scaled_size = svg.size * transform.scale.xy();
-                let origin_translation = origin.compute_translation(scaled_size);
+                let origin_translation =
+                    origin.compute_translation_in(scaled_bounds(*origin_bounds, slices, fit));
 
                 let mut gtransf = global_transform.compute_transform();
                 gtransf.translation.x += origin_translation.x;
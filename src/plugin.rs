@@ -15,9 +15,10 @@ use bevy::{
     app::{App, Plugin},
     asset::{AssetEvent, Assets},
     ecs::{
+        change_detection::DetectChanges,
         entity::Entity,
         event::EventReader,
-        query::{Added, Changed, Or},
+        query::Changed,
         schedule::{IntoSystemConfigs, SystemSet},
         system::{Commands, Query, Res, ResMut},
     },
@@ -27,16 +28,24 @@ use bevy::{
     render::mesh::Mesh,
 };
 
-#[cfg(feature = "2d")]
-use bevy::render::mesh::Mesh2d;
+#[cfg(all(feature = "2d", feature = "3d"))]
+use bevy::ecs::query::Or;
 
 #[cfg(feature = "3d")]
-use bevy::render::mesh::Mesh3d;
+use crate::billboard;
+#[cfg(feature = "2d")]
+use crate::render::Svg2d;
+#[cfg(feature = "3d")]
+use crate::render::Svg3d;
+#[cfg(feature = "2d")]
+use crate::{fit::SvgFit, mapped_mesh, slice::SvgSlices, sprite};
 
 use crate::{
+    lod::{self, SvgLodMeshes},
     origin,
-    render::{self, Svg2d, Svg3d},
-    svg::Svg,
+    render::{self, SvgComponents, SvgMeshes},
+    retessellation::{self, SvgTessellationTasks},
+    svg::{StrokeScaling, Svg},
 };
 
 /// Set in which [`Svg`](crate::prelude::Svg2d)s get drawn.
@@ -48,36 +57,88 @@ pub struct SvgRenderPlugin;
 
 impl Plugin for SvgRenderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(PostUpdate, origin::add_origin_state.in_set(SvgSet))
-            .add_systems(Last, (origin::apply_origin, svg_mesh_linker.in_set(SvgSet)))
+        app.init_resource::<SvgLodMeshes>()
+            .init_resource::<SvgTessellationTasks>()
+            .add_systems(PostUpdate, origin::add_origin_state.in_set(SvgSet))
+            .add_systems(
+                Last,
+                (
+                    origin::apply_origin,
+                    (
+                        apply_stroke_scaling,
+                        retessellation::retessellate_modified,
+                        retessellation::finish_tessellations,
+                        svg_mesh_linker,
+                        lod::apply_lod,
+                    )
+                        .chain()
+                        .in_set(SvgSet),
+                ),
+            )
             .add_plugins(render::SvgPlugin);
+
+        #[cfg(feature = "2d")]
+        app.add_systems(PostUpdate, sprite::apply_svg_sprites.in_set(SvgSet))
+            .add_systems(
+                Last,
+                (
+                    mapped_mesh::warn_combined_mappings,
+                    mapped_mesh::apply_mesh_mapping::<SvgSlices>,
+                    mapped_mesh::apply_mesh_mapping::<SvgFit>,
+                )
+                    .after(lod::apply_lod)
+                    .in_set(SvgSet),
+            );
+        #[cfg(feature = "3d")]
+        app.add_systems(Last, billboard::apply_billboard.after(origin::apply_origin));
     }
 }
 
+type SvgMeshComponents = (Entity, SvgComponents, SvgMeshes);
+
 #[cfg(feature = "2d")]
 #[cfg(not(feature = "3d"))]
-type SvgMeshComponents = (
-    Entity,
-    &'static Handle<Svg>,
-    Option<&'static mut Mesh2dHandle>,
-    Option<()>,
-);
+type ChangedSvg = Changed<Svg2d>;
 #[cfg(not(feature = "2d"))]
 #[cfg(feature = "3d")]
-type SvgMeshComponents = (
-    Entity,
-    &'static Handle<Svg>,
-    Option<()>,
-    Option<&'static mut Handle<Mesh>>,
-);
+type ChangedSvg = Changed<Svg3d>;
 #[cfg(all(feature = "2d", feature = "3d"))]
-type SvgMeshComponents = (
-    Entity,
-    Option<&'static Svg2d>,
-    Option<&'static Svg3d>,
-    Option<&'static mut Mesh2d>,
-    Option<&'static mut Mesh3d>,
-);
+type ChangedSvg = Or<(Changed<Svg2d>, Changed<Svg3d>)>;
+
+/// Bevy system which applies the [`StrokeScaling`] resource, if there is one, to all [`Svg`]s.
+fn apply_stroke_scaling(
+    stroke_scaling: Option<Res<StrokeScaling>>,
+    mut svg_events: EventReader<AssetEvent<Svg>>,
+    mut svgs: ResMut<Assets<Svg>>,
+) {
+    let Some(stroke_scaling) = stroke_scaling else {
+        svg_events.clear();
+        return;
+    };
+    let ids = if stroke_scaling.is_changed() {
+        svg_events.clear();
+        svgs.ids().collect::<Vec<_>>()
+    } else {
+        svg_events
+            .read()
+            .filter_map(|event| match event {
+                AssetEvent::LoadedWithDependencies { id } => Some(*id),
+                _ => None,
+            })
+            .collect()
+    };
+    for id in ids {
+        // Only access the asset mutably if needed, as that marks it as modified
+        if svgs
+            .get(id)
+            .is_some_and(|svg| svg.stroke_scaling != *stroke_scaling)
+        {
+            if let Some(svg) = svgs.get_mut(id) {
+                svg.stroke_scaling = *stroke_scaling;
+            }
+        }
+    }
+}
 
 /// Bevy system which queries for all [`Svg`] bundles and adds the correct [`Mesh`] to them.
 fn svg_mesh_linker(
@@ -86,69 +147,44 @@ fn svg_mesh_linker(
     mut meshes: ResMut<Assets<Mesh>>,
     svgs: Res<Assets<Svg>>,
     mut query: Query<SvgMeshComponents>,
-    changed_handles: Query<
-        Entity,
-        Or<(Changed<Svg2d>, Changed<Svg3d>, Added<Svg2d>, Added<Svg3d>)>,
-    >,
+    changed_handles: Query<Entity, ChangedSvg>,
 ) {
     for event in svg_events.read() {
         match event {
             AssetEvent::Added { .. } => (),
             AssetEvent::LoadedWithDependencies { id } => {
-                for (.., mesh_2d, mesh_3d) in query.iter_mut().filter(|(_, svg_2d, svg_3d, ..)| {
-                    svg_2d
-                        .map(|x| x.0.id() == *id)
-                        .or_else(|| svg_3d.map(|x| x.0.id() == *id))
-                        .unwrap_or(false)
-                }) {
+                for (_, _, svg_meshes) in query
+                    .iter_mut()
+                    .filter(|(_, svg, _)| render::svg_handle(*svg).is_some_and(|x| x.id() == *id))
+                {
                     let svg = svgs.get(*id).unwrap();
                     debug!(
                         "Svg `{}` created. Adding mesh component to entity.",
                         svg.name
                     );
-                    #[cfg(feature = "2d")]
-                    if let Some(mut mesh) = mesh_2d {
-                        mesh.0 = svg.mesh.clone();
-                    }
-                    #[cfg(feature = "3d")]
-                    if let Some(mut mesh) = mesh_3d {
-                        mesh.0 = svg.mesh.clone();
-                    }
+                    render::replace_svg_mesh(svg_meshes, &svg.mesh);
                 }
             }
             AssetEvent::Modified { id } => {
-                for (.., mesh_2d, mesh_3d) in query.iter_mut().filter(|(_, svg_2d, svg_3d, ..)| {
-                    svg_2d
-                        .map(|x| x.0.id() == *id)
-                        .or_else(|| svg_3d.map(|x| x.0.id() == *id))
-                        .unwrap_or(false)
-                }) {
+                for (_, _, svg_meshes) in query
+                    .iter_mut()
+                    .filter(|(_, svg, _)| render::svg_handle(*svg).is_some_and(|x| x.id() == *id))
+                {
                     let svg = svgs.get(*id).unwrap();
                     debug!(
                         "Svg `{}` modified. Changing mesh component of entity.",
                         svg.name
                     );
-                    #[cfg(feature = "2d")]
-                    if let Some(mut mesh) = mesh_2d.filter(|mesh| mesh.0 != svg.mesh) {
-                        let old_mesh = mesh.0.clone();
-                        mesh.0 = svg.mesh.clone();
-                        meshes.remove(&old_mesh);
-                    }
-                    #[cfg(feature = "3d")]
-                    if let Some(mut mesh) = mesh_3d.filter(|mesh| mesh.0 != svg.mesh) {
-                        let old_mesh = mesh.clone();
-                        mesh.0 = svg.mesh.clone();
+                    if let Some(old_mesh) = render::replace_svg_mesh(svg_meshes, &svg.mesh) {
                         meshes.remove(&old_mesh);
                     }
                 }
             }
             AssetEvent::Removed { id } => {
-                for (entity, ..) in query.iter_mut().filter(|(_, svg_2d, svg_3d, ..)| {
-                    svg_2d
-                        .map(|x| x.0.id() == *id)
-                        .or_else(|| svg_3d.map(|x| x.0.id() == *id))
-                        .unwrap_or(false)
-                }) {
+                for (entity, ..) in query
+                    .iter_mut()
+                    .filter(|(_, svg, _)| render::svg_handle(*svg).is_some_and(|x| x.id() == *id))
+                {
                     commands.entity(entity).despawn_recursive();
                 }
             }
@@ -160,26 +196,16 @@ fn svg_mesh_linker(
 
     // Ensure all correct meshes are set for entities which have had modified handles
     for entity in changed_handles.iter() {
-        let Ok((.., svg_2d, svg_3d, mesh_2d, mesh_3d)) = query.get_mut(entity) else {
+        let Ok((_, svg, svg_meshes)) = query.get_mut(entity) else {
             continue;
         };
-        let Some(handle) = svg_2d.map_or_else(|| svg_3d.map(|x| &x.0), |x| Some(&x.0)) else {
-            continue;
-        };
-        let Some(svg) = svgs.get(handle) else {
+        let Some(svg) = render::svg_handle(svg).and_then(|handle| svgs.get(handle)) else {
             continue;
         };
         debug!(
             "Svg handle for entity `{:?}` modified. Changing mesh component of entity.",
             entity
         );
-        #[cfg(feature = "2d")]
-        if let Some(mut mesh) = mesh_2d {
-            mesh.0 = svg.mesh.clone();
-        }
-        #[cfg(feature = "3d")]
-        if let Some(mut mesh) = mesh_3d {
-            mesh.0 = svg.mesh.clone();
-        }
+        render::replace_svg_mesh(svg_meshes, &svg.mesh);
     }
 }
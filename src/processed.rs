@@ -0,0 +1,798 @@
+//! Asset processing of SVGs into a pre-tessellated binary format.
+//!
+//! When the [`AssetPlugin`](bevy::asset::AssetPlugin) runs in processed mode, every SVG is parsed and
+//! tessellated once by the [`SvgAssetProcessor`] and stored as a compact binary containing its
+//! [`PathDescriptor`]s and tessellated [`VertexBuffers`]. The [`ProcessedSvgAssetLoader`] reads that
+//! binary back at runtime without involving `usvg` or `lyon`.
+//!
+//! The `image` sub-asset of [`SvgLoaderSettings::image_size`](crate::prelude::SvgLoaderSettings::image_size)
+//! is rasterized during processing and stored as well. The SVG document itself isn't stored, so
+//! processed SVGs rasterize their paths instead, and their elements can't be rasterized with
+//! `Svg::rasterize_element` or `SvgAtlasBuilder::add_element`.
+
+use bevy::{
+    asset::{
+        io::{Reader, Writer},
+        processor::LoadTransformAndSave,
+        saver::{AssetSaver, SavedAsset},
+        transformer::IdentityAssetTransformer,
+        AssetLoader, AsyncWriteExt, LoadContext,
+    },
+    color::{Color, ColorToComponents, Srgba},
+    log::debug,
+    math::{Rect, UVec2, Vec2},
+    render::alpha::AlphaMode,
+    utils::ConditionalSendFuture,
+};
+#[cfg(feature = "rasterize")]
+use bevy::{
+    image::Image,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use lyon_path::{math::Point, PathEvent};
+use lyon_tessellation::{FillTessellator, LineCap, LineJoin, StrokeOptions, StrokeTessellator};
+use std::sync::Arc;
+use svgtypes::{Align, AspectRatio, ViewBox};
+use thiserror::Error;
+
+use crate::{
+    loader::SvgAssetLoader,
+    render::{
+        tessellation,
+        vertex_buffer::{Vertex, VertexBuffers},
+    },
+    svg::{
+        DrawType, PathDescriptor, StrokeScaling, Svg, SvgAntiAliasing, SvgCurves, SvgSides,
+        SvgVertexAttributes,
+    },
+    Convert,
+};
+
+/// Processor that loads an SVG with the [`SvgAssetLoader`] and stores it with the [`SvgAssetSaver`].
+pub type SvgAssetProcessor =
+    LoadTransformAndSave<SvgAssetLoader, IdentityAssetTransformer<Svg>, SvgAssetSaver>;
+
+const MAGIC: &[u8; 4] = b"BSVG";
+/// Version of the binary format, which has to be increased with every change to its layout once
+/// it was released. Until then, it stays at 1 and processed assets have to be reprocessed.
+const VERSION: u32 = 1;
+
+/// Saves an [`Svg`] together with its tessellated geometry in a binary format.
+#[derive(Default)]
+pub struct SvgAssetSaver;
+
+impl AssetSaver for SvgAssetSaver {
+    type Asset = Svg;
+    type Settings = ();
+    type OutputLoader = ProcessedSvgAssetLoader;
+    type Error = ProcessedSvgError;
+
+    fn save(
+        &self,
+        writer: &mut Writer,
+        asset: SavedAsset<'_, Self::Asset>,
+        _settings: &(),
+    ) -> impl ConditionalSendFuture<Output = Result<(), Self::Error>> {
+        Box::pin(async move {
+            debug!("Saving processed SVG: {} ...", asset.name);
+            let buffers = tessellation::generate_buffer(
+                &asset,
+                None,
+                &mut FillTessellator::new(),
+                &mut StrokeTessellator::new(),
+            );
+
+            let mut encoder = Encoder::default();
+            encoder.bytes(MAGIC);
+            encoder.u32(VERSION);
+            encoder.svg(&asset)?;
+            encoder.buffers(&buffers)?;
+            #[cfg(feature = "rasterize")]
+            let image = asset
+                .get_labeled::<Image, _>("image")
+                .map(|image| (image.get().size(), image.get().data.as_slice()));
+            #[cfg(not(feature = "rasterize"))]
+            let image = None;
+            encoder.image(image)?;
+            writer.write_all(&encoder.0).await?;
+            debug!("Saving processed SVG: {} ... Done", asset.name);
+
+            Ok(())
+        })
+    }
+}
+
+/// Loads an [`Svg`] that was stored by the [`SvgAssetSaver`].
+#[derive(Default)]
+pub struct ProcessedSvgAssetLoader;
+
+impl AssetLoader for ProcessedSvgAssetLoader {
+    type Asset = Svg;
+    type Settings = ();
+    type Error = ProcessedSvgError;
+
+    fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            debug!(
+                "Loading processed SVG: {} ...",
+                load_context.path().display()
+            );
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            let mut decoder = Decoder(&bytes);
+            if decoder.bytes(MAGIC.len())? != MAGIC {
+                return Err(ProcessedSvgError::InvalidMagic);
+            }
+            let version = decoder.u32()?;
+            if version != VERSION {
+                return Err(ProcessedSvgError::UnsupportedVersion(version));
+            }
+            let mut svg = decoder.svg()?;
+            svg.tessellated_from = Some(svg.tessellation_hash());
+            let buffers = decoder.buffers()?;
+            let image = decoder.image()?;
+
+            let mesh = (
+                buffers,
+                Rect::from_corners(Vec2::ZERO, svg.size),
+                svg.vertex_attributes,
+            )
+                .convert();
+            svg.mesh = load_context.add_labeled_asset("mesh".to_string(), mesh);
+            #[cfg(feature = "rasterize")]
+            if let Some((size, data)) = image {
+                let image = Image::new(
+                    Extent3d {
+                        width: size.x,
+                        height: size.y,
+                        depth_or_array_layers: 1,
+                    },
+                    TextureDimension::D2,
+                    data.to_vec(),
+                    TextureFormat::Rgba8UnormSrgb,
+                    RenderAssetUsages::default(),
+                );
+                load_context.add_labeled_asset("image".to_string(), image);
+            }
+            #[cfg(not(feature = "rasterize"))]
+            let _ = image;
+            debug!(
+                "Loading processed SVG: {} ... Done",
+                load_context.path().display()
+            );
+
+            Ok(svg)
+        })
+    }
+}
+
+/// An error that occurs when saving or loading a processed SVG.
+#[derive(Error, Debug)]
+pub enum ProcessedSvgError {
+    /// The processed SVG could not be read or written.
+    #[error("could not read or write processed SVG: {0}")]
+    IoError(#[from] std::io::Error),
+    /// The file is not a processed SVG.
+    #[error("not a processed SVG")]
+    InvalidMagic,
+    /// The processed SVG was written by an incompatible version of `bevy_svg`.
+    #[error("unsupported processed SVG version {0}")]
+    UnsupportedVersion(u32),
+    /// The SVG has more elements than a processed SVG can store.
+    #[error("SVG is too large to be processed")]
+    TooLarge,
+    /// The processed SVG ended unexpectedly.
+    #[error("processed SVG ended unexpectedly")]
+    UnexpectedEnd,
+    /// The processed SVG contains invalid data.
+    #[error("processed SVG contains invalid data: {0}")]
+    InvalidData(&'static str),
+}
+
+#[derive(Default)]
+struct Encoder(Vec<u8>);
+
+impl Encoder {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn len(&mut self, len: usize) -> Result<(), ProcessedSvgError> {
+        self.u32(u32::try_from(len).map_err(|_| ProcessedSvgError::TooLarge)?);
+        Ok(())
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn str(&mut self, value: &str) -> Result<(), ProcessedSvgError> {
+        self.len(value.len())?;
+        self.bytes(value.as_bytes());
+        Ok(())
+    }
+
+    fn point(&mut self, point: Point) {
+        self.f32(point.x);
+        self.f32(point.y);
+    }
+
+    fn color(&mut self, color: Color) {
+        color
+            .to_srgba()
+            .to_f32_array()
+            .into_iter()
+            .for_each(|x| self.f32(x));
+    }
+
+    fn svg(&mut self, svg: &Svg) -> Result<(), ProcessedSvgError> {
+        self.str(&svg.name)?;
+        self.f32(svg.size.x);
+        self.f32(svg.size.y);
+        for value in [
+            svg.view_box.x,
+            svg.view_box.y,
+            svg.view_box.w,
+            svg.view_box.h,
+        ] {
+            self.f64(value);
+        }
+        self.u8(svg.preserve_aspect_ratio.defer.into());
+        self.u8(match svg.preserve_aspect_ratio.align {
+            Align::None => 0,
+            Align::XMinYMin => 1,
+            Align::XMidYMin => 2,
+            Align::XMaxYMin => 3,
+            Align::XMinYMid => 4,
+            Align::XMidYMid => 5,
+            Align::XMaxYMid => 6,
+            Align::XMinYMax => 7,
+            Align::XMidYMax => 8,
+            Align::XMaxYMax => 9,
+        });
+        self.u8(svg.preserve_aspect_ratio.slice.into());
+        for value in [
+            svg.content_bounds.min.x,
+            svg.content_bounds.min.y,
+            svg.content_bounds.max.x,
+            svg.content_bounds.max.y,
+        ] {
+            self.f32(value);
+        }
+        let (alpha_mode, alpha_cutoff) = match svg.alpha_mode {
+            AlphaMode::Opaque => (0, 0.0),
+            AlphaMode::Mask(cutoff) => (1, cutoff),
+            AlphaMode::Blend => (2, 0.0),
+            AlphaMode::Premultiplied => (3, 0.0),
+            AlphaMode::AlphaToCoverage => (4, 0.0),
+            AlphaMode::Add => (5, 0.0),
+            AlphaMode::Multiply => (6, 0.0),
+        };
+        self.u8(alpha_mode);
+        self.f32(alpha_cutoff);
+        self.u8(match svg.sides {
+            SvgSides::Front => 0,
+            SvgSides::DoubleSided => 1,
+            SvgSides::MirroredBack => 2,
+        });
+        self.f32(svg.depth_offset);
+        self.u8(match svg.stroke_scaling {
+            StrokeScaling::Scaled => 0,
+            StrokeScaling::ScreenSpace => 1,
+        });
+        self.u8(match svg.anti_aliasing {
+            SvgAntiAliasing::None => 0,
+            SvgAntiAliasing::Fringe => 1,
+        });
+        self.u8(match svg.curves {
+            SvgCurves::Flattened => 0,
+            SvgCurves::Analytic => 1,
+        });
+        self.u8(match svg.vertex_attributes {
+            SvgVertexAttributes::Svg => 0,
+            SvgVertexAttributes::Standard => 1,
+        });
+
+        self.len(svg.paths.len())?;
+        for path in svg.paths.iter() {
+            self.path(path)?;
+        }
+        Ok(())
+    }
+
+    fn path(&mut self, path: &PathDescriptor) -> Result<(), ProcessedSvgError> {
+        self.str(&path.id)?;
+        self.color(path.color);
+        let transform = path.abs_transform;
+        for value in [
+            transform.sx,
+            transform.ky,
+            transform.kx,
+            transform.sy,
+            transform.tx,
+            transform.ty,
+        ] {
+            self.f32(value);
+        }
+        self.u8(path.is_stroke.into());
+        self.u8(path.non_scaling_stroke.into());
+        match path.draw_type {
+            DrawType::Fill => self.u8(0),
+            DrawType::Stroke(opts) => {
+                self.u8(1);
+                self.u8(line_cap_to_u8(opts.start_cap));
+                self.u8(line_cap_to_u8(opts.end_cap));
+                self.u8(match opts.line_join {
+                    LineJoin::Miter => 0,
+                    LineJoin::MiterClip => 1,
+                    LineJoin::Round => 2,
+                    LineJoin::Bevel => 3,
+                });
+                self.f32(opts.line_width);
+                self.f32(opts.miter_limit);
+                self.f32(opts.tolerance);
+            }
+        }
+
+        self.len(path.segments.len())?;
+        for segment in &path.segments {
+            match *segment {
+                PathEvent::Begin { at } => {
+                    self.u8(0);
+                    self.point(at);
+                }
+                PathEvent::Line { from, to } => {
+                    self.u8(1);
+                    self.point(from);
+                    self.point(to);
+                }
+                PathEvent::Quadratic { from, ctrl, to } => {
+                    self.u8(2);
+                    self.point(from);
+                    self.point(ctrl);
+                    self.point(to);
+                }
+                PathEvent::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => {
+                    self.u8(3);
+                    self.point(from);
+                    self.point(ctrl1);
+                    self.point(ctrl2);
+                    self.point(to);
+                }
+                PathEvent::End { last, first, close } => {
+                    self.u8(4);
+                    self.point(last);
+                    self.point(first);
+                    self.u8(close.into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn buffers(&mut self, buffers: &VertexBuffers) -> Result<(), ProcessedSvgError> {
+        self.len(buffers.vertices.len())?;
+        for vertex in &buffers.vertices {
+            vertex.position.into_iter().for_each(|x| self.f32(x));
+            vertex.color.into_iter().for_each(|x| self.f32(x));
+            vertex.stroke_offset.into_iter().for_each(|x| self.f32(x));
+            vertex.fringe_offset.into_iter().for_each(|x| self.f32(x));
+            vertex.curve.into_iter().for_each(|x| self.f32(x));
+        }
+        self.len(buffers.indices.len())?;
+        for idx in &buffers.indices {
+            self.u32(*idx);
+        }
+        Ok(())
+    }
+
+    /// Writes the size and the `Rgba8UnormSrgb` pixels of the image the SVG was rasterized into,
+    /// if there is one.
+    fn image(&mut self, image: Option<(UVec2, &[u8])>) -> Result<(), ProcessedSvgError> {
+        let Some((size, data)) = image else {
+            self.u8(0);
+            return Ok(());
+        };
+        self.u8(1);
+        self.u32(size.x);
+        self.u32(size.y);
+        self.len(data.len())?;
+        self.bytes(data);
+        Ok(())
+    }
+}
+
+fn line_cap_to_u8(cap: LineCap) -> u8 {
+    match cap {
+        LineCap::Butt => 0,
+        LineCap::Square => 1,
+        LineCap::Round => 2,
+    }
+}
+
+fn line_cap_from_u8(value: u8) -> Result<LineCap, ProcessedSvgError> {
+    match value {
+        0 => Ok(LineCap::Butt),
+        1 => Ok(LineCap::Square),
+        2 => Ok(LineCap::Round),
+        _ => Err(ProcessedSvgError::InvalidData("line cap")),
+    }
+}
+
+struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], ProcessedSvgError> {
+        if self.0.len() < len {
+            return Err(ProcessedSvgError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ProcessedSvgError> {
+        Ok(self
+            .bytes(N)?
+            .try_into()
+            .expect("slice has the correct length"))
+    }
+
+    fn u8(&mut self) -> Result<u8, ProcessedSvgError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool, ProcessedSvgError> {
+        Ok(self.u8()? != 0)
+    }
+
+    fn u32(&mut self) -> Result<u32, ProcessedSvgError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn len(&mut self) -> Result<usize, ProcessedSvgError> {
+        Ok(self.u32()? as usize)
+    }
+
+    fn f32(&mut self) -> Result<f32, ProcessedSvgError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    fn f64(&mut self) -> Result<f64, ProcessedSvgError> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+
+    fn str(&mut self) -> Result<String, ProcessedSvgError> {
+        let len = self.len()?;
+        String::from_utf8(self.bytes(len)?.to_vec())
+            .map_err(|_| ProcessedSvgError::InvalidData("string"))
+    }
+
+    fn point(&mut self) -> Result<Point, ProcessedSvgError> {
+        Ok(Point::new(self.f32()?, self.f32()?))
+    }
+
+    fn color(&mut self) -> Result<Color, ProcessedSvgError> {
+        let rgba = [self.f32()?, self.f32()?, self.f32()?, self.f32()?];
+        Ok(Color::Srgba(Srgba::from_f32_array(rgba)))
+    }
+
+    fn svg(&mut self) -> Result<Svg, ProcessedSvgError> {
+        let name = self.str()?;
+        let size = Vec2::new(self.f32()?, self.f32()?);
+        let view_box = ViewBox {
+            x: self.f64()?,
+            y: self.f64()?,
+            w: self.f64()?,
+            h: self.f64()?,
+        };
+        let defer = self.bool()?;
+        let align = match self.u8()? {
+            0 => Align::None,
+            1 => Align::XMinYMin,
+            2 => Align::XMidYMin,
+            3 => Align::XMaxYMin,
+            4 => Align::XMinYMid,
+            5 => Align::XMidYMid,
+            6 => Align::XMaxYMid,
+            7 => Align::XMinYMax,
+            8 => Align::XMidYMax,
+            9 => Align::XMaxYMax,
+            _ => return Err(ProcessedSvgError::InvalidData("align")),
+        };
+        let slice = self.bool()?;
+        let content_bounds = Rect {
+            min: Vec2::new(self.f32()?, self.f32()?),
+            max: Vec2::new(self.f32()?, self.f32()?),
+        };
+        let alpha_mode = self.u8()?;
+        let alpha_cutoff = self.f32()?;
+        let alpha_mode = match alpha_mode {
+            0 => AlphaMode::Opaque,
+            1 => AlphaMode::Mask(alpha_cutoff),
+            2 => AlphaMode::Blend,
+            3 => AlphaMode::Premultiplied,
+            4 => AlphaMode::AlphaToCoverage,
+            5 => AlphaMode::Add,
+            6 => AlphaMode::Multiply,
+            _ => return Err(ProcessedSvgError::InvalidData("alpha mode")),
+        };
+        let sides = match self.u8()? {
+            0 => SvgSides::Front,
+            1 => SvgSides::DoubleSided,
+            2 => SvgSides::MirroredBack,
+            _ => return Err(ProcessedSvgError::InvalidData("sides")),
+        };
+        let depth_offset = self.f32()?;
+        let stroke_scaling = match self.u8()? {
+            0 => StrokeScaling::Scaled,
+            1 => StrokeScaling::ScreenSpace,
+            _ => return Err(ProcessedSvgError::InvalidData("stroke scaling")),
+        };
+        let anti_aliasing = match self.u8()? {
+            0 => SvgAntiAliasing::None,
+            1 => SvgAntiAliasing::Fringe,
+            _ => return Err(ProcessedSvgError::InvalidData("anti-aliasing")),
+        };
+        let curves = match self.u8()? {
+            0 => SvgCurves::Flattened,
+            1 => SvgCurves::Analytic,
+            _ => return Err(ProcessedSvgError::InvalidData("curves")),
+        };
+        let vertex_attributes = match self.u8()? {
+            0 => SvgVertexAttributes::Svg,
+            1 => SvgVertexAttributes::Standard,
+            _ => return Err(ProcessedSvgError::InvalidData("vertex attributes")),
+        };
+
+        let len = self.len()?;
+        let mut paths = Vec::with_capacity(len.min(self.0.len()));
+        for _ in 0..len {
+            paths.push(self.path()?);
+        }
+
+        Ok(Svg {
+            name,
+            size,
+            view_box,
+            preserve_aspect_ratio: AspectRatio {
+                defer,
+                align,
+                slice,
+            },
+            content_bounds,
+            paths: Arc::new(paths),
+            mesh: Default::default(),
+            alpha_mode,
+            sides,
+            depth_offset,
+            stroke_scaling,
+            anti_aliasing,
+            curves,
+            vertex_attributes,
+            #[cfg(feature = "rasterize")]
+            tree: None,
+            tessellated_from: None,
+        })
+    }
+
+    fn path(&mut self) -> Result<PathDescriptor, ProcessedSvgError> {
+        let id = self.str()?;
+        let color = self.color()?;
+        let abs_transform = usvg::Transform::from_row(
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+            self.f32()?,
+        );
+        let is_stroke = self.bool()?;
+        let non_scaling_stroke = self.bool()?;
+        let draw_type = match self.u8()? {
+            0 => DrawType::Fill,
+            1 => {
+                let mut opts = StrokeOptions::default();
+                opts.start_cap = line_cap_from_u8(self.u8()?)?;
+                opts.end_cap = line_cap_from_u8(self.u8()?)?;
+                opts.line_join = match self.u8()? {
+                    0 => LineJoin::Miter,
+                    1 => LineJoin::MiterClip,
+                    2 => LineJoin::Round,
+                    3 => LineJoin::Bevel,
+                    _ => return Err(ProcessedSvgError::InvalidData("line join")),
+                };
+                opts.line_width = self.f32()?;
+                opts.miter_limit = self.f32()?;
+                opts.tolerance = self.f32()?;
+                DrawType::Stroke(opts)
+            }
+            _ => return Err(ProcessedSvgError::InvalidData("draw type")),
+        };
+
+        let len = self.len()?;
+        let mut segments = Vec::with_capacity(len.min(self.0.len()));
+        for _ in 0..len {
+            segments.push(match self.u8()? {
+                0 => PathEvent::Begin { at: self.point()? },
+                1 => PathEvent::Line {
+                    from: self.point()?,
+                    to: self.point()?,
+                },
+                2 => PathEvent::Quadratic {
+                    from: self.point()?,
+                    ctrl: self.point()?,
+                    to: self.point()?,
+                },
+                3 => PathEvent::Cubic {
+                    from: self.point()?,
+                    ctrl1: self.point()?,
+                    ctrl2: self.point()?,
+                    to: self.point()?,
+                },
+                4 => PathEvent::End {
+                    last: self.point()?,
+                    first: self.point()?,
+                    close: self.bool()?,
+                },
+                _ => return Err(ProcessedSvgError::InvalidData("path event")),
+            });
+        }
+
+        Ok(PathDescriptor {
+            id,
+            segments,
+            color,
+            draw_type,
+            abs_transform,
+            is_stroke,
+            non_scaling_stroke,
+        })
+    }
+
+    fn buffers(&mut self) -> Result<VertexBuffers, ProcessedSvgError> {
+        let mut buffers = VertexBuffers::new();
+        let len = self.len()?;
+        buffers.vertices.reserve(len.min(self.0.len()));
+        for _ in 0..len {
+            buffers.vertices.push(Vertex {
+                position: [self.f32()?, self.f32()?, self.f32()?],
+                color: [self.f32()?, self.f32()?, self.f32()?, self.f32()?],
+                stroke_offset: [self.f32()?, self.f32()?],
+                fringe_offset: [self.f32()?, self.f32()?],
+                curve: [self.f32()?, self.f32()?, self.f32()?],
+            });
+        }
+        let len = self.len()?;
+        buffers.indices.reserve(len.min(self.0.len()));
+        for _ in 0..len {
+            let idx = self.u32()?;
+            if idx as usize >= buffers.vertices.len() {
+                return Err(ProcessedSvgError::InvalidData("index out of bounds"));
+            }
+            buffers.indices.push(idx);
+        }
+
+        Ok(buffers)
+    }
+
+    fn image(&mut self) -> Result<Option<(UVec2, &'a [u8])>, ProcessedSvgError> {
+        if !self.bool()? {
+            return Ok(None);
+        }
+        let size = UVec2::new(self.u32()?, self.u32()?);
+        let len = self.len()?;
+        if len as u64 != u64::from(size.x) * u64::from(size.y) * 4 {
+            return Err(ProcessedSvgError::InvalidData("image size"));
+        }
+        Ok(Some((size, self.bytes(len)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-10 -5 120 60" preserveAspectRatio="xMaxYMin slice">
+        <path id="fill" d="M 0 0 L 50 0 Q 60 25 50 50 Z" fill="#ff8000" fill-opacity="0.5"/>
+        <path id="stroke" d="M 60 10 C 70 0 90 20 100 10" fill="none" stroke="#0080ff" stroke-width="4" stroke-linecap="round" stroke-linejoin="bevel" transform="rotate(15)" vector-effect="non-scaling-stroke"/>
+    </svg>"##;
+
+    #[test]
+    fn round_trip() {
+        let mut svg: Svg = SVG.parse().unwrap();
+        svg.alpha_mode = AlphaMode::Mask(0.25);
+        svg.sides = SvgSides::MirroredBack;
+        svg.depth_offset = 0.5;
+        svg.stroke_scaling = StrokeScaling::ScreenSpace;
+        svg.anti_aliasing = SvgAntiAliasing::Fringe;
+        svg.curves = SvgCurves::Analytic;
+        svg.vertex_attributes = SvgVertexAttributes::Standard;
+        let buffers = tessellation::generate_buffer(
+            &svg,
+            None,
+            &mut FillTessellator::new(),
+            &mut StrokeTessellator::new(),
+        );
+
+        let mut encoder = Encoder::default();
+        encoder.svg(&svg).unwrap();
+        encoder.buffers(&buffers).unwrap();
+        let pixels = [255, 128, 0, 255, 0, 0, 0, 0];
+        encoder.image(Some((UVec2::new(2, 1), &pixels))).unwrap();
+        let mut decoder = Decoder(&encoder.0);
+        let decoded = decoder.svg().unwrap();
+        let decoded_buffers = decoder.buffers().unwrap();
+        let decoded_image = decoder.image().unwrap();
+        assert!(decoder.0.is_empty());
+
+        assert_eq!(decoded.name, svg.name);
+        assert_eq!(decoded.size, svg.size);
+        assert_eq!(decoded.view_box, svg.view_box);
+        assert_eq!(decoded.preserve_aspect_ratio, svg.preserve_aspect_ratio);
+        assert_eq!(decoded.content_bounds, svg.content_bounds);
+        assert_eq!(decoded.alpha_mode, svg.alpha_mode);
+        assert_eq!(decoded.sides, svg.sides);
+        assert_eq!(decoded.depth_offset, svg.depth_offset);
+        assert_eq!(decoded.stroke_scaling, svg.stroke_scaling);
+        assert_eq!(decoded.anti_aliasing, svg.anti_aliasing);
+        assert_eq!(decoded.curves, svg.curves);
+        assert_eq!(decoded.vertex_attributes, svg.vertex_attributes);
+        assert_eq!(decoded.paths.len(), svg.paths.len());
+        for (decoded, path) in decoded.paths.iter().zip(svg.paths.iter()) {
+            assert_eq!(decoded.id, path.id);
+            assert_eq!(decoded.color.to_srgba(), path.color.to_srgba());
+            assert_eq!(decoded.abs_transform, path.abs_transform);
+            assert_eq!(decoded.is_stroke, path.is_stroke);
+            assert_eq!(decoded.non_scaling_stroke, path.non_scaling_stroke);
+            assert_eq!(
+                format!("{:?}", decoded.draw_type),
+                format!("{:?}", path.draw_type)
+            );
+            assert_eq!(decoded.segments, path.segments);
+        }
+
+        assert!(!buffers.indices.is_empty());
+        assert_eq!(decoded_buffers.vertices, buffers.vertices);
+        assert_eq!(decoded_buffers.indices, buffers.indices);
+        assert_eq!(decoded_image, Some((UVec2::new(2, 1), pixels.as_slice())));
+    }
+
+    #[test]
+    fn truncated() {
+        let svg: Svg = SVG.parse().unwrap();
+        let mut encoder = Encoder::default();
+        encoder.svg(&svg).unwrap();
+        let bytes = &encoder.0[..encoder.0.len() - 1];
+        assert!(matches!(
+            Decoder(bytes).svg(),
+            Err(ProcessedSvgError::UnexpectedEnd)
+        ));
+    }
+}
@@ -0,0 +1,188 @@
+//! Rasterization of SVGs into images on the CPU, with [`resvg`].
+
+use bevy::{
+    color::ColorToPacked,
+    image::Image,
+    log::debug,
+    math::Vec2,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use lyon_path::PathEvent;
+use lyon_tessellation::{LineCap, LineJoin};
+use resvg::tiny_skia::{self, FillRule, Paint, PathBuilder, Pixmap, Stroke, Transform};
+use svgtypes::{Align, AspectRatio};
+
+use crate::{
+    svg::{DrawType, PathDescriptor, Svg},
+    util::aspect,
+};
+
+impl Svg {
+    /// Renders the SVG into an image of the given size in pixels, on the CPU with `resvg`.
+    ///
+    /// The SVG is stretched to fill the whole image, a size of zero is increased to one pixel.
+    /// The image is in `Rgba8UnormSrgb` with straight alpha, so it can be used for sprites,
+    /// UI images, window icons and cursors.
+    ///
+    /// SVGs that are loaded pre-tessellated by the asset processor don't have their document
+    /// anymore. Their paths are rendered instead, which looks the same as their mesh, so
+    /// gradients are averaged and images are missing.
+    pub fn rasterize(&self, width: u32, height: u32) -> Image {
+        self.rasterize_with_aspect_ratio(
+            width,
+            height,
+            AspectRatio {
+                defer: false,
+                align: Align::None,
+                slice: false,
+            },
+        )
+    }
+
+    /// Renders the SVG into an image of the given size in pixels, like [`Svg::rasterize`], but
+    /// fits it into the image following the rules of `preserveAspectRatio`.
+    ///
+    /// Pass [`Svg::preserve_aspect_ratio`] to fit the SVG the same way it fits its `viewBox`.
+    /// The parts of the image that aren't covered by the SVG are transparent.
+    pub fn rasterize_with_aspect_ratio(
+        &self,
+        width: u32,
+        height: u32,
+        aspect_ratio: AspectRatio,
+    ) -> Image {
+        debug!("Rasterizing SVG `{}` at {width}x{height} ...", self.name);
+        let (width, height) = (width.max(1), height.max(1));
+        let mut pixmap = Pixmap::new(width, height).expect("the size is not zero");
+        let (scale, offset) = aspect::fit(
+            self.size.max(Vec2::splat(f32::EPSILON)),
+            Vec2::new(width as f32, height as f32),
+            aspect_ratio,
+        );
+        let transform = Transform::from_row(scale.x, 0.0, 0.0, scale.y, offset.x, offset.y);
+
+        if let Some(tree) = &self.tree {
+            resvg::render(tree, transform, &mut pixmap.as_mut());
+        } else {
+            for path in self.paths.iter() {
+                render_path(path, transform, &mut pixmap);
+            }
+        }
+
+        debug!("Rasterizing SVG `{}` ... Done", self.name);
+
+        pixmap_to_image(&pixmap)
+    }
+
+    /// Renders the element with the given `id`, and its children, into an image of the given
+    /// size in pixels, like [`Svg::rasterize`]. The bounding box of the element is fitted into
+    /// the image, centered and without changing its aspect ratio.
+    ///
+    /// Returns `None` if there is no element with this `id`, or if it is empty. SVGs that are
+    /// loaded pre-tessellated by the asset processor, or whose paths were edited, don't have
+    /// their document anymore, so their elements can't be rendered either.
+    pub fn rasterize_element(&self, id: &str, width: u32, height: u32) -> Option<Image> {
+        let node = self.tree.as_ref()?.node_by_id(id)?;
+        let bounds = node.abs_layer_bounding_box()?;
+        debug!(
+            "Rasterizing element `{id}` of SVG `{}` at {width}x{height} ...",
+            self.name
+        );
+        let (width, height) = (width.max(1), height.max(1));
+        let mut pixmap = Pixmap::new(width, height).expect("the size is not zero");
+        let (scale, offset) = aspect::fit(
+            Vec2::new(bounds.width(), bounds.height()),
+            Vec2::new(width as f32, height as f32),
+            AspectRatio {
+                defer: false,
+                align: Align::XMidYMid,
+                slice: false,
+            },
+        );
+        // The element is moved to the origin by `resvg`
+        let transform = Transform::from_row(scale.x, 0.0, 0.0, scale.y, offset.x, offset.y);
+        resvg::render_node(node, transform, &mut pixmap.as_mut())?;
+        debug!("Rasterizing element `{id}` of SVG `{}` ... Done", self.name);
+
+        Some(pixmap_to_image(&pixmap))
+    }
+}
+
+/// Converts a pixmap into an `Rgba8UnormSrgb` image.
+fn pixmap_to_image(pixmap: &Pixmap) -> Image {
+    // Pixmaps are premultiplied, images are not
+    let data = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+
+    Image::new(
+        Extent3d {
+            width: pixmap.width(),
+            height: pixmap.height(),
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// Renders a single [`PathDescriptor`], the way it is tessellated.
+fn render_path(path: &PathDescriptor, transform: Transform, pixmap: &mut Pixmap) {
+    let mut builder = PathBuilder::new();
+    for event in &path.segments {
+        match *event {
+            PathEvent::Begin { at } => builder.move_to(at.x, at.y),
+            PathEvent::Line { to, .. } => builder.line_to(to.x, to.y),
+            PathEvent::Quadratic { ctrl, to, .. } => builder.quad_to(ctrl.x, ctrl.y, to.x, to.y),
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => builder.cubic_to(ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y),
+            PathEvent::End { close: true, .. } => builder.close(),
+            PathEvent::End { close: false, .. } => (),
+        }
+    }
+    let Some(skia_path) = builder.finish() else {
+        return;
+    };
+
+    let [r, g, b, a] = path.color.to_srgba().to_u8_array();
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(r, g, b, a);
+    paint.anti_alias = true;
+
+    let transform = transform.pre_concat(path.abs_transform);
+    match path.draw_type {
+        // The fill tessellator uses the even-odd rule as well
+        DrawType::Fill => {
+            pixmap.fill_path(&skia_path, &paint, FillRule::EvenOdd, transform, None);
+        }
+        DrawType::Stroke(opts) => {
+            let stroke = Stroke {
+                width: opts.line_width,
+                miter_limit: opts.miter_limit,
+                line_cap: match opts.start_cap {
+                    LineCap::Butt => tiny_skia::LineCap::Butt,
+                    LineCap::Square => tiny_skia::LineCap::Square,
+                    LineCap::Round => tiny_skia::LineCap::Round,
+                },
+                line_join: match opts.line_join {
+                    LineJoin::Miter => tiny_skia::LineJoin::Miter,
+                    LineJoin::MiterClip => tiny_skia::LineJoin::MiterClip,
+                    LineJoin::Round => tiny_skia::LineJoin::Round,
+                    LineJoin::Bevel => tiny_skia::LineJoin::Bevel,
+                },
+                dash: None,
+            };
+            pixmap.stroke_path(&skia_path, &paint, &stroke, transform, None);
+        }
+    }
+}
@@ -1,6 +1,19 @@
+#[cfg(feature = "2d")]
+use bevy::render::mesh::Mesh2d;
+#[cfg(feature = "3d")]
+use bevy::render::mesh::Mesh3d;
+use bevy::{asset::Handle, render::render_resource::Shader};
+#[cfg(any(feature = "2d", feature = "3d"))]
+use bevy::{ecs::query::QueryItem, render::mesh::Mesh};
+
+#[cfg(any(feature = "2d", feature = "3d"))]
+use crate::svg::Svg;
+
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod msdf;
 mod plugin;
 pub mod tessellation;
-mod vertex_buffer;
+pub(crate) mod vertex_buffer;
 
 #[cfg(feature = "2d")]
 mod svg2d;
@@ -10,6 +23,74 @@ mod svg3d;
 #[cfg(feature = "2d")]
 pub use svg2d::{Svg2d, Svg2dBundle};
 #[cfg(feature = "3d")]
-pub use svg3d::{Svg3d, Svg3dBundle};
+pub use svg3d::{Svg3d, Svg3dBundle, Svg3dStandardMaterial};
 
+#[cfg(any(feature = "2d", feature = "3d"))]
+pub use msdf::SvgMsdfMaterial;
 pub use plugin::SvgPlugin;
+
+/// The [`Svg2d`] and [`Svg3d`] components of an entity, as far as their features are enabled.
+#[cfg(all(feature = "2d", feature = "3d"))]
+pub(crate) type SvgComponents = (Option<&'static Svg2d>, Option<&'static Svg3d>);
+#[cfg(all(feature = "2d", not(feature = "3d")))]
+pub(crate) type SvgComponents = Option<&'static Svg2d>;
+#[cfg(all(not(feature = "2d"), feature = "3d"))]
+pub(crate) type SvgComponents = Option<&'static Svg3d>;
+
+/// The [`Mesh2d`] and [`Mesh3d`] components of an entity, as far as their features are enabled.
+#[cfg(all(feature = "2d", feature = "3d"))]
+pub(crate) type SvgMeshes = (Option<&'static mut Mesh2d>, Option<&'static mut Mesh3d>);
+#[cfg(all(feature = "2d", not(feature = "3d")))]
+pub(crate) type SvgMeshes = Option<&'static mut Mesh2d>;
+#[cfg(all(not(feature = "2d"), feature = "3d"))]
+pub(crate) type SvgMeshes = Option<&'static mut Mesh3d>;
+
+/// The handle of the SVG shown by an entity with the given [`SvgComponents`].
+#[cfg(all(feature = "2d", feature = "3d"))]
+pub(crate) fn svg_handle<'w>(
+    (svg_2d, svg_3d): QueryItem<'w, SvgComponents>,
+) -> Option<&'w Handle<Svg>> {
+    svg_2d
+        .map(|svg_2d| &svg_2d.0)
+        .or_else(|| svg_3d.map(|svg_3d| &svg_3d.0))
+}
+/// The handle of the SVG shown by an entity with the given [`SvgComponents`].
+#[cfg(all(feature = "2d", not(feature = "3d")))]
+pub(crate) fn svg_handle<'w>(svg_2d: QueryItem<'w, SvgComponents>) -> Option<&'w Handle<Svg>> {
+    svg_2d.map(|svg_2d| &svg_2d.0)
+}
+/// The handle of the SVG shown by an entity with the given [`SvgComponents`].
+#[cfg(all(not(feature = "2d"), feature = "3d"))]
+pub(crate) fn svg_handle<'w>(svg_3d: QueryItem<'w, SvgComponents>) -> Option<&'w Handle<Svg>> {
+    svg_3d.map(|svg_3d| &svg_3d.0)
+}
+
+/// Replaces the mesh of an entity with the given [`SvgMeshes`], and returns the previous mesh
+/// if it was a different one.
+#[cfg(any(feature = "2d", feature = "3d"))]
+pub(crate) fn replace_svg_mesh(
+    meshes: QueryItem<'_, SvgMeshes>,
+    mesh: &Handle<Mesh>,
+) -> Option<Handle<Mesh>> {
+    #[cfg(all(feature = "2d", feature = "3d"))]
+    let (mesh_2d, mesh_3d) = meshes;
+    #[cfg(all(feature = "2d", not(feature = "3d")))]
+    let mesh_2d = meshes;
+    #[cfg(all(not(feature = "2d"), feature = "3d"))]
+    let mesh_3d = meshes;
+
+    let mut previous = None;
+    #[cfg(feature = "2d")]
+    if let Some(mut mesh_2d) = mesh_2d.filter(|mesh_2d| mesh_2d.0 != *mesh) {
+        previous = Some(std::mem::replace(&mut mesh_2d.0, mesh.clone()));
+    }
+    #[cfg(feature = "3d")]
+    if let Some(mut mesh_3d) = mesh_3d.filter(|mesh_3d| mesh_3d.0 != *mesh) {
+        previous = Some(std::mem::replace(&mut mesh_3d.0, mesh.clone()));
+    }
+    previous
+}
+
+/// Handle to the shader module with the material functions shared by the 2D and 3D shaders
+pub const SVG_MATERIAL_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(8_514_826_660_651_853_414);
@@ -0,0 +1,109 @@
+#[cfg(feature = "2d")]
+use bevy::sprite::{AlphaMode2d, Material2d, Material2dPlugin};
+use bevy::{
+    app::{App, Plugin},
+    asset::{load_internal_asset, Asset, Handle},
+    color::LinearRgba,
+    image::Image,
+    reflect::{std_traits::ReflectDefault, Reflect},
+    render::render_resource::{AsBindGroup, Shader, ShaderRef},
+};
+#[cfg(feature = "3d")]
+use bevy::{
+    pbr::{Material, MaterialPipeline, MaterialPipelineKey, MaterialPlugin},
+    render::{
+        alpha::AlphaMode,
+        mesh::MeshVertexBufferLayoutRef,
+        render_resource::{RenderPipelineDescriptor, SpecializedMeshPipelineError},
+    },
+};
+
+/// Handle to the shader that renders multi-channel signed distance fields
+pub const SVG_MSDF_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(8_514_826_660_651_853_415);
+
+/// Material that renders the fills of an SVG from its multi-channel signed distance field,
+/// generated with [`Svg::generate_msdf`](crate::svg::Svg::generate_msdf), with edges that stay
+/// crisp at any zoom.
+///
+/// The field is stretched over the UVs of the mesh, so it is drawn on a `Rectangle` with the
+/// size of the SVG, with `MeshMaterial2d` in 2D or `MeshMaterial3d` in 3D. Everything is drawn
+/// in a single [`color`](SvgMsdfMaterial::color).
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[reflect(Default, Debug)]
+pub struct SvgMsdfMaterial {
+    /// Color of the fills.
+    #[uniform(0)]
+    pub color: LinearRgba,
+    /// The `distance_range` the field was generated with.
+    #[uniform(0)]
+    pub distance_range: f32,
+    /// The field, generated with [`Svg::generate_msdf`](crate::svg::Svg::generate_msdf).
+    #[texture(1)]
+    #[sampler(2)]
+    pub texture: Handle<Image>,
+}
+
+impl Default for SvgMsdfMaterial {
+    fn default() -> Self {
+        Self {
+            color: LinearRgba::WHITE,
+            distance_range: 4.0,
+            texture: Handle::default(),
+        }
+    }
+}
+
+/// Plugin that renders [`SvgMsdfMaterial`]s in 2D and 3D
+pub struct RenderPlugin;
+
+impl Plugin for RenderPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SVG_MSDF_SHADER_HANDLE,
+            "svg_msdf.wgsl",
+            Shader::from_wgsl
+        );
+
+        #[cfg(feature = "2d")]
+        app.add_plugins(Material2dPlugin::<SvgMsdfMaterial>::default());
+        #[cfg(feature = "3d")]
+        app.add_plugins(MaterialPlugin::<SvgMsdfMaterial>::default());
+    }
+}
+
+#[cfg(feature = "2d")]
+impl Material2d for SvgMsdfMaterial {
+    fn fragment_shader() -> ShaderRef {
+        SVG_MSDF_SHADER_HANDLE.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+#[cfg(feature = "3d")]
+impl Material for SvgMsdfMaterial {
+    fn fragment_shader() -> ShaderRef {
+        SVG_MSDF_SHADER_HANDLE.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        AlphaMode::Blend
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // The shader imports the vertex output of `bevy_pbr` instead of the one of `bevy_sprite`
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader_defs.push("SVG_MSDF_3D".into());
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,12 @@
-use crate::resources::{FillTessellator, StrokeTessellator};
-use bevy::app::{App, Plugin};
+use crate::render::SVG_MATERIAL_SHADER_HANDLE;
+use bevy::{
+    app::{App, Plugin},
+    asset::load_internal_asset,
+    render::render_resource::Shader,
+};
 
+#[cfg(any(feature = "2d", feature = "3d"))]
+use crate::render::msdf;
 #[cfg(feature = "2d")]
 use crate::render::svg2d;
 #[cfg(feature = "3d")]
@@ -11,14 +17,20 @@ pub struct SvgPlugin;
 
 impl Plugin for SvgPlugin {
     fn build(&self, app: &mut App) {
-        let fill_tess = FillTessellator::default();
-        let stroke_tess = StrokeTessellator::default();
-        app.insert_resource(fill_tess).insert_resource(stroke_tess);
+        load_internal_asset!(
+            app,
+            SVG_MATERIAL_SHADER_HANDLE,
+            "svg_material.wgsl",
+            Shader::from_wgsl
+        );
 
         #[cfg(feature = "2d")]
         app.add_plugins(svg2d::RenderPlugin);
 
         #[cfg(feature = "3d")]
         app.add_plugins(svg3d::RenderPlugin);
+
+        #[cfg(any(feature = "2d", feature = "3d"))]
+        app.add_plugins(msdf::RenderPlugin);
     }
 }
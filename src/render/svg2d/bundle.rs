@@ -9,7 +9,7 @@ use bevy::{
     transform::components::{GlobalTransform, Transform},
 };
 
-use crate::origin::Origin;
+use crate::origin::{Origin, OriginBounds};
 
 use super::Svg2d;
 
@@ -25,6 +25,8 @@ pub struct Svg2dBundle {
     pub mesh_2d: Mesh2d,
     /// [`Origin`] of the coordinate system and as such the origin for the Bevy position.
     pub origin: Origin,
+    /// [`OriginBounds`] the [`Origin`] is relative to.
+    pub origin_bounds: OriginBounds,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
     pub visibility: Visibility,
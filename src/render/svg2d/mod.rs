@@ -1,4 +1,7 @@
-use crate::{origin::Origin, svg::Svg};
+use crate::{
+    origin::{Origin, OriginBounds},
+    svg::Svg,
+};
 use bevy::{
     asset::Handle,
     ecs::{
@@ -21,7 +24,7 @@ pub use plugin::RenderPlugin;
 
 /// A component for 2D SVGs.
 #[derive(Component, Default)]
-#[require(Mesh2d, Origin)]
+#[require(Mesh2d, Origin, OriginBounds)]
 #[component(on_insert = svg_2d_on_insert)]
 pub struct Svg2d(pub Handle<Svg>);
 
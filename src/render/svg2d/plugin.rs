@@ -1,11 +1,20 @@
 use bevy::{
     app::{App, Plugin},
     asset::{load_internal_asset, AssetApp},
-    render::render_resource::{Shader, ShaderRef},
-    sprite::{Material2d, Material2dPlugin},
+    render::{
+        alpha::AlphaMode,
+        mesh::{Mesh, MeshVertexBufferLayoutRef},
+        render_resource::{
+            RenderPipelineDescriptor, Shader, ShaderRef, SpecializedMeshPipelineError,
+        },
+    },
+    sprite::{AlphaMode2d, Material2d, Material2dKey, Material2dPlugin},
 };
 
-use crate::{render::svg2d::SVG_2D_SHADER_HANDLE, svg::Svg};
+use crate::{
+    render::svg2d::SVG_2D_SHADER_HANDLE,
+    svg::{Svg, SvgAntiAliasing, SvgCurves},
+};
 
 /// Plugin that renders [`Svg`](crate::svg::Svg)s in 2D
 pub struct RenderPlugin;
@@ -20,7 +29,78 @@ impl Plugin for RenderPlugin {
 }
 
 impl Material2d for Svg {
+    fn vertex_shader() -> ShaderRef {
+        SVG_2D_SHADER_HANDLE.into()
+    }
+
     fn fragment_shader() -> ShaderRef {
         SVG_2D_SHADER_HANDLE.into()
     }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        // 2D only supports a subset of the alpha modes, everything translucent is blended
+        match self.render_alpha_mode() {
+            AlphaMode::Opaque => AlphaMode2d::Opaque,
+            AlphaMode::Mask(cutoff) => AlphaMode2d::Mask(cutoff),
+            AlphaMode::Blend
+            | AlphaMode::Premultiplied
+            | AlphaMode::AlphaToCoverage
+            | AlphaMode::Add
+            | AlphaMode::Multiply => AlphaMode2d::Blend,
+        }
+    }
+
+    fn specialize(
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        key: Material2dKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // Only strokes that keep their width on screen have stroke offsets
+        let screen_space_strokes = layout.0.contains(Svg::ATTRIBUTE_STROKE_OFFSET);
+        let anti_aliasing_fringe = key.bind_group_data.anti_aliasing == SvgAntiAliasing::Fringe
+            && layout.0.contains(Svg::ATTRIBUTE_FRINGE_OFFSET);
+        let analytic_curves = key.bind_group_data.curves == SvgCurves::Analytic
+            && layout.0.contains(Svg::ATTRIBUTE_CURVE);
+        if screen_space_strokes || anti_aliasing_fringe || analytic_curves {
+            // The vertex layout of `Mesh2dPipeline` with the stroke and fringe offsets and the
+            // curve coordinates added
+            let mut attributes = vec![Mesh::ATTRIBUTE_POSITION.at_shader_location(0)];
+            for (attribute, location) in [
+                (Mesh::ATTRIBUTE_NORMAL, 1),
+                (Mesh::ATTRIBUTE_UV_0, 2),
+                (Mesh::ATTRIBUTE_TANGENT, 3),
+                (Mesh::ATTRIBUTE_COLOR, 4),
+            ] {
+                if layout.0.contains(attribute) {
+                    attributes.push(attribute.at_shader_location(location));
+                }
+            }
+            if screen_space_strokes {
+                attributes.push(Svg::ATTRIBUTE_STROKE_OFFSET.at_shader_location(5));
+                descriptor
+                    .vertex
+                    .shader_defs
+                    .push("SVG_SCREEN_SPACE_STROKES".into());
+            }
+            if anti_aliasing_fringe {
+                attributes.push(Svg::ATTRIBUTE_FRINGE_OFFSET.at_shader_location(6));
+                descriptor
+                    .vertex
+                    .shader_defs
+                    .push("SVG_ANTI_ALIASING_FRINGE".into());
+            }
+            if analytic_curves {
+                attributes.push(Svg::ATTRIBUTE_CURVE.at_shader_location(7));
+                descriptor
+                    .vertex
+                    .shader_defs
+                    .push("SVG_ANALYTIC_CURVES".into());
+                if let Some(fragment) = descriptor.fragment.as_mut() {
+                    fragment.shader_defs.push("SVG_ANALYTIC_CURVES".into());
+                }
+            }
+            descriptor.vertex.buffers = vec![layout.0.get_layout(&attributes)?];
+        }
+        Ok(())
+    }
 }
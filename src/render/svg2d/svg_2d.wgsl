@@ -1,8 +1,9 @@
 #import bevy_sprite::{
-    mesh2d_types::Mesh2d,
+    mesh2d_functions as mesh_functions,
     mesh2d_view_bindings::view,
     mesh2d_vertex_output::VertexOutput,
 }
+#import bevy_svg::material::{SvgMaterial, alpha_discard, anti_aliasing_fringe, curve_coverage, pack_curve, screen_space_stroke}
 
 #ifdef TONEMAP_IN_SHADER
 #import bevy_core_pipeline::tonemapping
@@ -10,12 +11,102 @@
 
 
 @group(2) @binding(0)
-var<uniform> mesh: Mesh2d;
+var<uniform> material: SvgMaterial;
+
+// Same as the `Vertex` of `bevy_sprite::mesh2d`, with the stroke and fringe offsets
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+#ifdef VERTEX_POSITIONS
+    @location(0) position: vec3<f32>,
+#endif
+#ifdef VERTEX_NORMALS
+    @location(1) normal: vec3<f32>,
+#endif
+#ifdef VERTEX_UVS
+    @location(2) uv: vec2<f32>,
+#endif
+#ifdef VERTEX_TANGENTS
+    @location(3) tangent: vec4<f32>,
+#endif
+#ifdef VERTEX_COLORS
+    @location(4) color: vec4<f32>,
+#endif
+#ifdef SVG_SCREEN_SPACE_STROKES
+    @location(5) stroke_offset: vec2<f32>,
+#endif
+#ifdef SVG_ANTI_ALIASING_FRINGE
+    @location(6) fringe_offset: vec2<f32>,
+#endif
+#ifdef SVG_ANALYTIC_CURVES
+    @location(7) curve: vec3<f32>,
+#endif
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+#ifdef VERTEX_UVS
+    out.uv = vertex.uv;
+#endif
+#ifdef SVG_ANALYTIC_CURVES
+    out.uv = pack_curve(vertex.curve);
+#endif
+
+#ifdef VERTEX_POSITIONS
+    var world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    var position = vertex.position;
+#ifdef SVG_SCREEN_SPACE_STROKES
+    position = screen_space_stroke(
+        position,
+        vertex.stroke_offset,
+        world_from_local,
+        view.clip_from_world,
+        view.clip_from_view,
+        view.viewport,
+    );
+#endif
+#ifdef SVG_ANTI_ALIASING_FRINGE
+    position = anti_aliasing_fringe(
+        position,
+        vertex.fringe_offset,
+        world_from_local,
+        view.clip_from_world,
+        view.clip_from_view,
+        view.viewport,
+    );
+#endif
+    out.world_position = mesh_functions::mesh2d_position_local_to_world(
+        world_from_local,
+        vec4<f32>(position, 1.0)
+    );
+    out.position = mesh_functions::mesh2d_position_world_to_clip(out.world_position);
+#endif
+
+#ifdef VERTEX_NORMALS
+    out.world_normal = mesh_functions::mesh2d_normal_local_to_world(vertex.normal, vertex.instance_index);
+#endif
+
+#ifdef VERTEX_TANGENTS
+    out.world_tangent = mesh_functions::mesh2d_tangent_local_to_world(
+        world_from_local,
+        vertex.tangent
+    );
+#endif
+
+#ifdef VERTEX_COLORS
+    out.color = vertex.color;
+#endif
+    return out;
+}
 
 @fragment
 fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
 #ifdef VERTEX_COLORS
     var color = in.color;
+#ifdef SVG_ANALYTIC_CURVES
+    color = curve_coverage(material, color, in.uv);
+#endif
+    color = alpha_discard(material, color);
 #ifdef TONEMAP_IN_SHADER
     color = tonemapping::tone_mapping(color, view.color_grading);
 #endif
@@ -4,7 +4,7 @@ use bevy::{
         component::{Component, ComponentId},
         world::DeferredWorld,
     },
-    pbr::MeshMaterial3d,
+    pbr::{MeshMaterial3d, StandardMaterial},
     prelude::Entity,
     render::{mesh::Mesh3d, render_resource::Shader},
 };
@@ -14,22 +14,67 @@ mod plugin;
 
 /// Handle to the custom shader with a unique random ID
 pub const SVG_3D_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(8_514_826_640_451_853_414);
+/// Handle to the prepass and shadow shader, which mirrors the back of the SVG like the main shader
+pub const SVG_3D_PREPASS_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(8_514_826_640_451_853_415);
 
 pub use bundle::Svg3dBundle;
 pub use plugin::RenderPlugin;
 
-use crate::{origin::Origin, svg::Svg};
+use crate::{
+    origin::{Origin, OriginBounds},
+    svg::Svg,
+};
 
 /// A component for 3D SVGs.
 #[derive(Component, Default)]
-#[require(Mesh3d, Origin, MeshMaterial3d<Svg>)]
+#[require(Mesh3d, Origin, OriginBounds, MeshMaterial3d<Svg>)]
 #[component(on_insert = svg_3d_on_insert)]
 pub struct Svg3d(pub Handle<Svg>);
 
+/// Renders an [`Svg3d`] with a [`StandardMaterial`] instead of the unlit SVG shader, so it is lit
+/// and can cast and receive shadows.
+///
+/// The vertex colors of the SVG are multiplied with the `base_color` of the material, so a white
+/// material shows the SVG in its original colors. The SVG needs
+/// [`SvgVertexAttributes::Standard`](crate::prelude::SvgVertexAttributes::Standard) for the normals,
+/// tangents and UVs of the material.
+#[derive(Component, Clone, Debug, Default)]
+#[component(
+    on_insert = svg_3d_on_insert,
+    on_remove = svg_3d_standard_material_on_remove
+)]
+pub struct Svg3dStandardMaterial(pub Handle<StandardMaterial>);
+
 fn svg_3d_on_insert(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
-    let component = world.entity(entity).get_components::<&Svg3d>().unwrap();
-    let handle = component.0.clone();
-    let entity = world.entity(entity).id();
+    let Some(svg_handle) = world.get::<Svg3d>(entity).map(|svg| svg.0.clone()) else {
+        return;
+    };
+    let standard_handle = world
+        .get::<Svg3dStandardMaterial>(entity)
+        .map(|material| material.0.clone());
+    let mut commands = world.commands();
+    if let Some(handle) = standard_handle {
+        commands
+            .entity(entity)
+            .remove::<MeshMaterial3d<Svg>>()
+            .insert(MeshMaterial3d(handle));
+    } else {
+        commands.entity(entity).insert(MeshMaterial3d(svg_handle));
+    }
+}
+
+fn svg_3d_standard_material_on_remove(
+    mut world: DeferredWorld,
+    entity: Entity,
+    _component_id: ComponentId,
+) {
+    let svg_handle = world.get::<Svg3d>(entity).map(|svg| svg.0.clone());
     let mut commands = world.commands();
-    commands.entity(entity).insert(MeshMaterial3d(handle));
+    let mut entity = commands.entity(entity);
+    entity.remove::<MeshMaterial3d<StandardMaterial>>();
+    if let Some(handle) = svg_handle {
+        // The entity might be in the process of being despawned
+        entity.try_insert(MeshMaterial3d(handle));
+    }
 }
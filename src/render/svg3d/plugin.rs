@@ -1,13 +1,19 @@
 use bevy::{
     app::{App, Plugin},
     asset::{load_internal_asset, AssetApp},
-    pbr::{Material, MaterialPlugin},
-    render::render_resource::{Shader, ShaderRef},
+    pbr::{Material, MaterialPipeline, MaterialPipelineKey, MaterialPlugin},
+    render::{
+        alpha::AlphaMode,
+        mesh::{Mesh, MeshVertexBufferLayoutRef},
+        render_resource::{
+            Face, RenderPipelineDescriptor, Shader, ShaderRef, SpecializedMeshPipelineError,
+        },
+    },
 };
 
-use crate::svg::Svg;
+use crate::svg::{Svg, SvgAntiAliasing, SvgCurves, SvgSides};
 
-use super::SVG_3D_SHADER_HANDLE;
+use super::{SVG_3D_PREPASS_SHADER_HANDLE, SVG_3D_SHADER_HANDLE};
 
 /// Plugin that renders [`Svg`](crate::svg::Svg)s in 2D
 pub struct RenderPlugin;
@@ -15,6 +21,12 @@ pub struct RenderPlugin;
 impl Plugin for RenderPlugin {
     fn build(&self, app: &mut App) {
         load_internal_asset!(app, SVG_3D_SHADER_HANDLE, "svg_3d.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            SVG_3D_PREPASS_SHADER_HANDLE,
+            "svg_3d_prepass.wgsl",
+            Shader::from_wgsl
+        );
 
         app.add_plugins(MaterialPlugin::<Svg>::default())
             .register_asset_reflect::<Svg>();
@@ -22,7 +34,92 @@ impl Plugin for RenderPlugin {
 }
 
 impl Material for Svg {
+    fn vertex_shader() -> ShaderRef {
+        SVG_3D_SHADER_HANDLE.into()
+    }
+
     fn fragment_shader() -> ShaderRef {
         SVG_3D_SHADER_HANDLE.into()
     }
+
+    fn prepass_vertex_shader() -> ShaderRef {
+        SVG_3D_PREPASS_SHADER_HANDLE.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        self.render_alpha_mode()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // Back faces are culled unless the SVG is double-sided. A mirrored back is turned around
+        // in the vertex shaders, which makes its faces front faces again.
+        descriptor.primitive.cull_mode = match key.bind_group_data.sides {
+            SvgSides::Front | SvgSides::MirroredBack => Some(Face::Back),
+            SvgSides::DoubleSided => None,
+        };
+        // Only strokes that keep their width on screen have stroke offsets
+        let screen_space_strokes = layout.0.contains(Svg::ATTRIBUTE_STROKE_OFFSET);
+        let anti_aliasing_fringe = key.bind_group_data.anti_aliasing == SvgAntiAliasing::Fringe
+            && layout.0.contains(Svg::ATTRIBUTE_FRINGE_OFFSET);
+        // The curve coordinates are passed on in place of the UVs
+        let analytic_curves = key.bind_group_data.curves == SvgCurves::Analytic
+            && layout.0.contains(Svg::ATTRIBUTE_CURVE)
+            && layout.0.contains(Mesh::ATTRIBUTE_UV_0);
+        // The prepass and shadow pipelines use the vertex layout of the `bevy_pbr` prepass
+        if (screen_space_strokes || anti_aliasing_fringe || analytic_curves)
+            && descriptor.vertex.shader == SVG_3D_SHADER_HANDLE
+        {
+            // The vertex layout of `MeshPipeline` with the stroke and fringe offsets and the
+            // curve coordinates added
+            let mut attributes = vec![Mesh::ATTRIBUTE_POSITION.at_shader_location(0)];
+            for (attribute, location) in [
+                (Mesh::ATTRIBUTE_NORMAL, 1),
+                (Mesh::ATTRIBUTE_UV_0, 2),
+                (Mesh::ATTRIBUTE_UV_1, 3),
+                (Mesh::ATTRIBUTE_TANGENT, 4),
+                (Mesh::ATTRIBUTE_COLOR, 5),
+            ] {
+                if layout.0.contains(attribute) {
+                    attributes.push(attribute.at_shader_location(location));
+                }
+            }
+            if screen_space_strokes {
+                attributes.push(Svg::ATTRIBUTE_STROKE_OFFSET.at_shader_location(8));
+                descriptor
+                    .vertex
+                    .shader_defs
+                    .push("SVG_SCREEN_SPACE_STROKES".into());
+            }
+            if anti_aliasing_fringe {
+                attributes.push(Svg::ATTRIBUTE_FRINGE_OFFSET.at_shader_location(9));
+                descriptor
+                    .vertex
+                    .shader_defs
+                    .push("SVG_ANTI_ALIASING_FRINGE".into());
+            }
+            if analytic_curves {
+                attributes.push(Svg::ATTRIBUTE_CURVE.at_shader_location(10));
+                descriptor
+                    .vertex
+                    .shader_defs
+                    .push("SVG_ANALYTIC_CURVES".into());
+                if let Some(fragment) = descriptor.fragment.as_mut() {
+                    fragment.shader_defs.push("SVG_ANALYTIC_CURVES".into());
+                }
+            }
+            descriptor.vertex.buffers = vec![layout.0.get_layout(&attributes)?];
+        }
+        if key.bind_group_data.sides == SvgSides::MirroredBack {
+            descriptor
+                .vertex
+                .shader_defs
+                .push("SVG_MIRRORED_BACK".into());
+        }
+        Ok(())
+    }
 }
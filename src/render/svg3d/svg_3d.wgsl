@@ -1,11 +1,131 @@
 #import bevy_pbr::{
-    forward_io::{VertexOutput, FragmentOutput}
-    mesh_types::Mesh,
-    mesh_view_bindings,
+    forward_io::{VertexOutput, FragmentOutput},
+    mesh_functions,
+    mesh_view_bindings::view,
+    view_transformations::position_world_to_clip,
 }
+#import bevy_svg::material::{SvgMaterial, alpha_discard, premultiply_alpha, anti_aliasing_fringe, curve_coverage, is_back_facing, mirror_back, pack_curve, screen_space_stroke}
 
 @group(2) @binding(0)
-var<uniform> mesh: Mesh;
+var<uniform> material: SvgMaterial;
+
+// Same as `bevy_pbr::forward_io::Vertex`, without skinning and morph targets but with the stroke and fringe offsets
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+#ifdef VERTEX_NORMALS
+    @location(1) normal: vec3<f32>,
+#endif
+#ifdef VERTEX_UVS_A
+    @location(2) uv: vec2<f32>,
+#endif
+#ifdef VERTEX_UVS_B
+    @location(3) uv_b: vec2<f32>,
+#endif
+#ifdef VERTEX_TANGENTS
+    @location(4) tangent: vec4<f32>,
+#endif
+#ifdef VERTEX_COLORS
+    @location(5) color: vec4<f32>,
+#endif
+#ifdef SVG_SCREEN_SPACE_STROKES
+    @location(8) stroke_offset: vec2<f32>,
+#endif
+#ifdef SVG_ANTI_ALIASING_FRINGE
+    @location(9) fringe_offset: vec2<f32>,
+#endif
+#ifdef SVG_ANALYTIC_CURVES
+    @location(10) curve: vec3<f32>,
+#endif
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    var position = vertex.position;
+#ifdef SVG_SCREEN_SPACE_STROKES
+    position = screen_space_stroke(
+        position,
+        vertex.stroke_offset,
+        world_from_local,
+        view.clip_from_world,
+        view.clip_from_view,
+        view.viewport,
+    );
+#endif
+#ifdef SVG_ANTI_ALIASING_FRINGE
+    position = anti_aliasing_fringe(
+        position,
+        vertex.fringe_offset,
+        world_from_local,
+        view.clip_from_world,
+        view.clip_from_view,
+        view.viewport,
+    );
+#endif
+#ifdef VERTEX_NORMALS
+    var normal = vertex.normal;
+#endif
+#ifdef VERTEX_TANGENTS
+    var tangent = vertex.tangent;
+#endif
+#ifdef SVG_MIRRORED_BACK
+    // The prepass and shadow shader mirror the SVG the same way
+    if is_back_facing(world_from_local, view.view_from_world, view.clip_from_view) {
+        position = mirror_back(position, material.mirror_x);
+#ifdef VERTEX_NORMALS
+        normal = mirror_back(normal, 0.0);
+#endif
+#ifdef VERTEX_TANGENTS
+        tangent = vec4<f32>(mirror_back(tangent.xyz, 0.0), tangent.w);
+#endif
+    }
+#endif
+
+#ifdef VERTEX_NORMALS
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(normal, vertex.instance_index);
+#endif
+
+#ifdef VERTEX_POSITIONS
+    out.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4<f32>(position, 1.0));
+    out.position = position_world_to_clip(out.world_position.xyz);
+#endif
+
+#ifdef VERTEX_UVS_A
+    out.uv = vertex.uv;
+#endif
+#ifdef SVG_ANALYTIC_CURVES
+    out.uv = pack_curve(vertex.curve);
+#endif
+#ifdef VERTEX_UVS_B
+    out.uv_b = vertex.uv_b;
+#endif
+
+#ifdef VERTEX_TANGENTS
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(
+        world_from_local,
+        tangent,
+        vertex.instance_index
+    );
+#endif
+
+#ifdef VERTEX_COLORS
+    out.color = vertex.color;
+#endif
+
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = vertex.instance_index;
+#endif
+
+#ifdef VISIBILITY_RANGE_DITHER
+    out.visibility_range_dither = mesh_functions::get_visibility_range_dither_level(
+        vertex.instance_index, world_from_local[3]);
+#endif
+
+    return out;
+}
 
 @fragment
 fn fragment(
@@ -13,6 +133,10 @@ fn fragment(
     @builtin(front_facing) is_front: bool,
 ) -> FragmentOutput {
     var out: FragmentOutput;
-    out.color = in.color;
+    var color = in.color;
+#ifdef SVG_ANALYTIC_CURVES
+    color = curve_coverage(material, color, in.uv);
+#endif
+    out.color = premultiply_alpha(material, alpha_discard(material, color));
     return out;
 }
@@ -0,0 +1,89 @@
+#import bevy_pbr::{
+    mesh_functions,
+    mesh_view_bindings::view,
+    prepass_io::{Vertex, VertexOutput},
+    view_transformations::position_world_to_clip,
+}
+#import bevy_svg::material::{SvgMaterial, is_back_facing, mirror_back}
+
+@group(2) @binding(0)
+var<uniform> material: SvgMaterial;
+
+// Same as the vertex shader of the `bevy_pbr` prepass, which is used for the depth, normal and
+// motion vector prepasses and for shadows, without skinning and morph targets, but with the
+// mirrored back of the SVG.
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    var position = vertex.position;
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+    var normal = vertex.normal;
+#ifdef VERTEX_TANGENTS
+    var tangent = vertex.tangent;
+#endif
+#endif
+#ifdef SVG_MIRRORED_BACK
+    // Needs to match the mirroring of the main pass, so the depth and the shadows are mirrored too
+    let is_mirrored = is_back_facing(world_from_local, view.view_from_world, view.clip_from_view);
+    if is_mirrored {
+        position = mirror_back(position, material.mirror_x);
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+        normal = mirror_back(normal, 0.0);
+#ifdef VERTEX_TANGENTS
+        tangent = vec4<f32>(mirror_back(tangent.xyz, 0.0), tangent.w);
+#endif
+#endif
+    }
+#endif
+
+    out.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4<f32>(position, 1.0));
+    out.position = position_world_to_clip(out.world_position.xyz);
+#ifdef DEPTH_CLAMP_ORTHO
+    out.clip_position_unclamped = out.position;
+    out.position.z = min(out.position.z, 1.0);
+#endif
+
+#ifdef VERTEX_UVS_A
+    out.uv = vertex.uv;
+#endif
+#ifdef VERTEX_UVS_B
+    out.uv_b = vertex.uv_b;
+#endif
+
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(normal, vertex.instance_index);
+#ifdef VERTEX_TANGENTS
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(
+        world_from_local,
+        tangent,
+        vertex.instance_index
+    );
+#endif
+#endif
+
+#ifdef VERTEX_COLORS
+    out.color = vertex.color;
+#endif
+
+#ifdef MOTION_VECTOR_PREPASS
+    // The previous position is mirrored like the current one, so mirroring itself doesn't move
+    var previous_position = vertex.position;
+#ifdef SVG_MIRRORED_BACK
+    if is_mirrored {
+        previous_position = mirror_back(previous_position, material.mirror_x);
+    }
+#endif
+    out.previous_world_position = mesh_functions::mesh_position_local_to_world(
+        mesh_functions::get_previous_world_from_local(vertex.instance_index),
+        vec4<f32>(previous_position, 1.0)
+    );
+#endif
+
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = vertex.instance_index;
+#endif
+
+    return out;
+}
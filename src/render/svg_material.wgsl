@@ -0,0 +1,135 @@
+#define_import_path bevy_svg::material
+
+// Needs to match `SvgMaterialUniform` and its `AsBindGroupShaderType` implementation
+struct SvgMaterial {
+    alpha_mode: u32,
+    alpha_cutoff: f32,
+    mirror_x: f32,
+};
+
+const ALPHA_MODE_OPAQUE: u32 = 0u;
+const ALPHA_MODE_MASK: u32 = 1u;
+const ALPHA_MODE_BLEND: u32 = 2u;
+const ALPHA_MODE_PREMULTIPLIED: u32 = 3u;
+const ALPHA_MODE_ALPHA_TO_COVERAGE: u32 = 4u;
+const ALPHA_MODE_ADD: u32 = 5u;
+const ALPHA_MODE_MULTIPLY: u32 = 6u;
+
+// Makes opaque colors fully opaque and discards fragments below the cutoff of `AlphaMode::Mask`.
+fn alpha_discard(material: SvgMaterial, color: vec4<f32>) -> vec4<f32> {
+    var output_color = color;
+    if material.alpha_mode == ALPHA_MODE_OPAQUE {
+        output_color.a = 1.0;
+    } else if material.alpha_mode == ALPHA_MODE_MASK {
+        if output_color.a < material.alpha_cutoff {
+            discard;
+        }
+        output_color.a = 1.0;
+    }
+    return output_color;
+}
+
+// Prepares the color for the blend state of `AlphaMode::Add` and `AlphaMode::Multiply`,
+// the same way `bevy_pbr` does it.
+fn premultiply_alpha(material: SvgMaterial, color: vec4<f32>) -> vec4<f32> {
+    if material.alpha_mode == ALPHA_MODE_ADD {
+        return vec4<f32>(color.rgb * color.a, 0.0);
+    } else if material.alpha_mode == ALPHA_MODE_MULTIPLY {
+        return vec4<f32>(color.rgb * color.a, color.a);
+    }
+    return color;
+}
+
+// Size of a physical pixel in local units at the given position, `w` is 1 for orthographic projections.
+fn local_per_pixel(
+    position: vec3<f32>,
+    world_from_local: mat4x4<f32>,
+    clip_from_world: mat4x4<f32>,
+    clip_from_view: mat4x4<f32>,
+    viewport: vec4<f32>,
+) -> f32 {
+    let clip_position = clip_from_world * world_from_local * vec4<f32>(position, 1.0);
+    let world_per_pixel = 2.0 * clip_position.w / (clip_from_view[1][1] * viewport.w);
+    let local_scale = 0.5 * (length(world_from_local[0].xyz) + length(world_from_local[1].xyz));
+    return world_per_pixel / local_scale;
+}
+
+// Scales the offset of a stroke vertex from the center line of its stroke, so that the stroke
+// has the same width in physical pixels, as it has in SVG units.
+fn screen_space_stroke(
+    position: vec3<f32>,
+    stroke_offset: vec2<f32>,
+    world_from_local: mat4x4<f32>,
+    clip_from_world: mat4x4<f32>,
+    clip_from_view: mat4x4<f32>,
+    viewport: vec4<f32>,
+) -> vec3<f32> {
+    let center = position - vec3<f32>(stroke_offset, 0.0);
+    let pixel = local_per_pixel(center, world_from_local, clip_from_world, clip_from_view, viewport);
+    return center + vec3<f32>(stroke_offset * pixel, 0.0);
+}
+
+// Moves the outer vertices of an anti-aliasing fringe away from the edge they fade out from,
+// by one physical pixel.
+fn anti_aliasing_fringe(
+    position: vec3<f32>,
+    fringe_offset: vec2<f32>,
+    world_from_local: mat4x4<f32>,
+    clip_from_world: mat4x4<f32>,
+    clip_from_view: mat4x4<f32>,
+    viewport: vec4<f32>,
+) -> vec3<f32> {
+    let pixel = local_per_pixel(position, world_from_local, clip_from_world, clip_from_view, viewport);
+    return position + vec3<f32>(fringe_offset * pixel, 0.0);
+}
+
+// Packs the curve coordinates `(u, v, side)` of a vertex into two components, which are passed to
+// the fragment shader in place of the UVs, as the SVG material doesn't use them. Curves that fill
+// the outside of the curve have their v-coordinate moved by 2.
+fn pack_curve(curve: vec3<f32>) -> vec2<f32> {
+    return vec2<f32>(curve.x, curve.y + select(0.0, 2.0, curve.z < 0.0));
+}
+
+// Fades out the color outside of a quadratic curve, given the packed curve coordinates of the
+// fragment, over one physical pixel. Opaque SVGs get a hard edge instead.
+fn curve_coverage(material: SvgMaterial, color: vec4<f32>, curve: vec2<f32>) -> vec4<f32> {
+    let fills_outside = curve.y > 1.5;
+    let v = select(curve.y, curve.y - 2.0, fills_outside);
+    let implicit = select(1.0, -1.0, fills_outside) * (curve.x * curve.x - v);
+    // The distance to the curve in pixels, approximated with the gradient of the implicit function.
+    // Fragments that aren't part of a curve have a constant implicit function of -1.
+    let gradient = vec2<f32>(dpdx(implicit), dpdy(implicit));
+    let distance = implicit / max(length(gradient), 1e-6);
+    let coverage = clamp(0.5 - distance, 0.0, 1.0);
+    if material.alpha_mode == ALPHA_MODE_OPAQUE {
+        if coverage < 0.5 {
+            discard;
+        }
+        return color;
+    } else if material.alpha_mode == ALPHA_MODE_PREMULTIPLIED {
+        return color * coverage;
+    }
+    return vec4<f32>(color.rgb, color.a * coverage);
+}
+
+// Whether an SVG, which lies in the xy-plane of its mesh, is seen from behind by the view.
+fn is_back_facing(
+    world_from_local: mat4x4<f32>,
+    view_from_world: mat4x4<f32>,
+    clip_from_view: mat4x4<f32>,
+) -> bool {
+    let normal = view_from_world * vec4<f32>(cross(world_from_local[0].xyz, world_from_local[1].xyz), 0.0);
+    let origin = view_from_world * vec4<f32>(world_from_local[3].xyz, 1.0);
+    // An orthographic view looks along its -z axis, a perspective one from its origin
+    let is_orthographic = clip_from_view[3].w == 1.0;
+    let to_view = select(-origin.xyz, vec3<f32>(0.0, 0.0, 1.0), is_orthographic);
+    return dot(normal.xyz, to_view) < 0.0;
+}
+
+// Turns a point of an SVG that is seen from behind by half a turn around the vertical line at
+// `mirror_x`, which makes the SVG readable, and its faces front faces again. The depth is
+// mirrored as well, so paths keep their order when offset in depth. Directions are turned with
+// a `mirror_x` of 0.
+fn mirror_back(point: vec3<f32>, mirror_x: f32) -> vec3<f32> {
+    return vec3<f32>(2.0 * mirror_x - point.x, point.y, -point.z);
+}
@@ -0,0 +1,36 @@
+#ifdef SVG_MSDF_3D
+#import bevy_pbr::forward_io::VertexOutput
+#else
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+#endif
+
+// Needs to match the uniform fields of `SvgMsdfMaterial`
+struct SvgMsdfMaterial {
+    color: vec4<f32>,
+    distance_range: f32,
+};
+
+@group(2) @binding(0)
+var<uniform> material: SvgMsdfMaterial;
+@group(2) @binding(1)
+var msdf_texture: texture_2d<f32>;
+@group(2) @binding(2)
+var msdf_sampler: sampler;
+
+fn median(a: f32, b: f32, c: f32) -> f32 {
+    return max(min(a, b), min(max(a, b), c));
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let field = textureSample(msdf_texture, msdf_sampler, in.uv);
+    let distance = median(field.r, field.g, field.b) - 0.5;
+
+    // The distance range in pixels on the screen, which keeps the edges one pixel wide at any zoom
+    let unit_range = vec2<f32>(material.distance_range) / vec2<f32>(textureDimensions(msdf_texture, 0));
+    let screen_texel_size = vec2<f32>(1.0) / fwidth(in.uv);
+    let screen_px_range = max(0.5 * dot(unit_range, screen_texel_size), 1.0);
+    let coverage = clamp(screen_px_range * distance + 0.5, 0.0, 1.0);
+
+    return vec4<f32>(material.color.rgb, material.color.a * coverage);
+}
@@ -1,64 +1,584 @@
-use bevy::log::{debug, error};
-use lyon_tessellation::{BuffersBuilder, FillOptions, FillTessellator, StrokeTessellator};
+use bevy::{
+    log::{debug, error},
+    math::Vec2,
+    tasks::{ComputeTaskPool, ParallelSlice},
+};
+use lyon_path::{
+    geom::{CubicBezierSegment, QuadraticBezierSegment},
+    math::Point,
+    Path, PathEvent, Side,
+};
+use lyon_tessellation::{
+    BuffersBuilder, FillOptions, FillTessellator, LineJoin, StrokeOptions, StrokeTessellator,
+};
+use std::cell::RefCell;
 
 use crate::{
-    render::vertex_buffer::{BufferExt, VertexBuffers, VertexConstructor},
-    svg::{DrawType, Svg},
+    render::vertex_buffer::{
+        BufferExt, FringeVertexConstructor, IndexType, VertexBuffers, VertexConstructor,
+    },
+    svg::{DrawType, PathDescriptor, StrokeScaling, Svg, SvgAntiAliasing, SvgCurves},
+    util::geometry::is_inside,
 };
 
+/// Number of paths below which an [`Svg`] is tessellated on the calling thread, as spreading
+/// a few paths over tasks costs more than it saves.
+const MIN_PATHS_PER_TASK: usize = 64;
+
+thread_local! {
+    /// Tessellators of the current thread, which keep their allocations between tessellations.
+    static TESSELLATORS: RefCell<(FillTessellator, StrokeTessellator)> =
+        RefCell::new((FillTessellator::new(), StrokeTessellator::new()));
+}
+
+/// Calls `f` with the tessellators of the current thread, or with new ones if they are already
+/// in use further up the stack.
+pub(crate) fn with_thread_tessellators<R>(
+    f: impl FnOnce(&mut FillTessellator, &mut StrokeTessellator) -> R,
+) -> R {
+    TESSELLATORS.with(|tessellators| match tessellators.try_borrow_mut() {
+        Ok(mut tessellators) => {
+            let (fill_tess, stroke_tess) = &mut *tessellators;
+            f(fill_tess, stroke_tess)
+        }
+        Err(_) => f(&mut FillTessellator::new(), &mut StrokeTessellator::new()),
+    })
+}
+
+/// Tessellates all paths of the [`Svg`].
+///
+/// If a `tolerance` is given, it replaces the tolerances of all fills and strokes. It is the
+/// maximum distance, in SVG units, between the tessellated and the real curves.
+///
+/// SVGs with many paths are tessellated in parallel on the [`ComputeTaskPool`], if it was
+/// initialized, with the tessellators of the threads the chunks of paths run on instead of the
+/// given ones.
 pub fn generate_buffer(
     svg: &Svg,
+    tolerance: Option<f32>,
     fill_tess: &mut FillTessellator,
     stroke_tess: &mut StrokeTessellator,
 ) -> VertexBuffers {
     debug!("Tessellating SVG: {}", svg.name);
 
-    let mut buffers = VertexBuffers::new();
+    let settings = TessellationSettings {
+        tolerance,
+        stroke_scaling: svg.stroke_scaling,
+        anti_aliasing: svg.anti_aliasing,
+        curves: svg.curves,
+    };
+
+    let tessellate = |index: usize,
+                      paths: &[PathDescriptor],
+                      fill_tess: &mut FillTessellator,
+                      stroke_tess: &mut StrokeTessellator| {
+        let buffers = paths
+            .iter()
+            .enumerate()
+            .map(|(idx, path)| {
+                generate_path_buffer(
+                    path,
+                    (index + idx) as f32 * svg.depth_offset,
+                    &settings,
+                    fill_tess,
+                    stroke_tess,
+                )
+            })
+            .collect::<Vec<_>>();
+        merge_buffers(buffers)
+    };
+
+    // Outside of an app, e.g. in tests, the task pool may not be initialized
+    let task_pool = ComputeTaskPool::try_get().filter(|task_pool| {
+        svg.paths.len() >= 2 * MIN_PATHS_PER_TASK && task_pool.thread_num() > 1
+    });
+    let buffers = if let Some(task_pool) = task_pool {
+        // A few chunks per thread, so threads with simple paths don't wait for the others
+        let chunk_size = svg
+            .paths
+            .len()
+            .div_ceil(task_pool.thread_num() * 4)
+            .max(MIN_PATHS_PER_TASK);
+        let buffers = svg
+            .paths
+            .par_chunk_map(task_pool, chunk_size, |chunk, paths| {
+                with_thread_tessellators(|fill_tess, stroke_tess| {
+                    tessellate(chunk * chunk_size, paths, fill_tess, stroke_tess)
+                })
+            });
+        merge_buffers(buffers)
+    } else {
+        tessellate(0, &svg.paths, fill_tess, stroke_tess)
+    };
+    debug!("Tessellating SVG: {} ... Done", svg.name);
+
+    buffers
+}
+
+/// Merges the buffers into one, in order, allocated once for all of them.
+fn merge_buffers(buffers: Vec<VertexBuffers>) -> VertexBuffers {
+    let (vertex_count, index_count) = buffers.iter().fold((0, 0), |(vertices, indices), buffer| {
+        (
+            vertices + buffer.vertices.len(),
+            indices + buffer.indices.len(),
+        )
+    });
+    let mut merged = VertexBuffers::with_capacity(vertex_count, index_count);
+    merged.extend(buffers);
+    merged
+}
+
+/// How the paths of an [`Svg`] are tessellated, which is the same for all of them.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TessellationSettings {
+    /// Replaces the tolerances of all fills and strokes, see [`generate_buffer`].
+    pub tolerance: Option<f32>,
+    /// See [`Svg::stroke_scaling`].
+    pub stroke_scaling: StrokeScaling,
+    /// See [`Svg::anti_aliasing`].
+    pub anti_aliasing: SvgAntiAliasing,
+    /// See [`Svg::curves`].
+    pub curves: SvgCurves,
+}
+
+/// Tessellates a single [`PathDescriptor`] into its own [`VertexBuffers`], with all vertices
+/// placed at the given depth.
+///
+/// Only strokes that keep their width on screen, with [`StrokeScaling::ScreenSpace`] or as
+/// non-scaling strokes, get stroke offsets.
+pub fn generate_path_buffer(
+    path: &PathDescriptor,
+    depth: f32,
+    settings: &TessellationSettings,
+    fill_tess: &mut FillTessellator,
+    stroke_tess: &mut StrokeTessellator,
+) -> VertexBuffers {
+    let TessellationSettings {
+        tolerance,
+        stroke_scaling,
+        anti_aliasing,
+        curves,
+    } = *settings;
+    let mut buffer = VertexBuffers::new();
+
+    // Lyon tessellates the path before it is transformed, so the tolerance is scaled into
+    // the coordinate system of the path
+    let tolerance = tolerance.map(|tolerance| {
+        let transform = path.abs_transform;
+        let scale = (transform.sx * transform.sy - transform.kx * transform.ky)
+            .abs()
+            .sqrt();
+        if scale > 0.0 {
+            tolerance / scale
+        } else {
+            tolerance
+        }
+    });
+    let segments = path.segments.iter().copied();
+    match path.draw_type {
+        DrawType::Fill if curves == SvgCurves::Analytic => {
+            buffer = generate_curve_fill_buffer(path, depth, tolerance, fill_tess);
+        }
+        DrawType::Fill => {
+            if let Err(e) = fill_tess.tessellate(
+                segments,
+                &FillOptions::tolerance(tolerance.unwrap_or(0.001)),
+                &mut BuffersBuilder::new(
+                    &mut buffer,
+                    VertexConstructor {
+                        color: path.color,
+                        transform: path.abs_transform,
+                        depth,
+                    },
+                ),
+            ) {
+                error!("FillTessellator error: {:?}", e);
+            }
+        }
+        DrawType::Stroke(opts) => {
+            if let Err(e) = stroke_tess.tessellate(
+                segments,
+                &tolerance.map_or(opts, |tolerance| opts.with_tolerance(tolerance)),
+                &mut BuffersBuilder::new(
+                    &mut buffer,
+                    VertexConstructor {
+                        color: path.color,
+                        transform: path.abs_transform,
+                        depth,
+                    },
+                ),
+            ) {
+                error!("StrokeTessellator error: {:?}", e);
+            }
+        }
+    }
+    if anti_aliasing == SvgAntiAliasing::Fringe {
+        buffer.extend_one(generate_fringe_buffer(path, depth, tolerance, stroke_tess));
+    }
+    if stroke_scaling == StrokeScaling::Scaled && !path.non_scaling_stroke {
+        // Vertices without an offset aren't moved by the shader
+        for vertex in &mut buffer.vertices {
+            vertex.stroke_offset = [0.0; 2];
+        }
+    }
+    orient_triangles(&mut buffer);
+
+    buffer
+}
+
+/// How often a curve of [`SvgCurves::Analytic`] is split at most, to keep its triangle from
+/// reaching across the rest of the fill.
+const MAX_CURVE_SPLITS: u32 = 6;
+
+/// A quadratic curve, or a line if it has no control point.
+type QuadraticSegment = (Point, Option<Point>, Point);
+
+/// Tessellates a fill for [`SvgCurves::Analytic`], with every quadratic curve as a single
+/// triangle spanned by its start, control and end point.
+///
+/// The polygon between the end points of the curves is filled by the tessellator. Curves that
+/// bulge out of it fill the part of their triangle inside of the curve. Curves that bulge into
+/// it take their triangle out of the polygon and fill the part of it outside of the curve. Curves
+/// whose control point is on the wrong side, because their triangle reaches across another edge,
+/// are split.
+fn generate_curve_fill_buffer(
+    path: &PathDescriptor,
+    depth: f32,
+    tolerance: Option<f32>,
+    fill_tess: &mut FillTessellator,
+) -> VertexBuffers {
+    let mut buffer = VertexBuffers::new();
+    let tolerance = tolerance.unwrap_or(0.001);
+
+    let contours = quadratic_contours(path, tolerance);
+    let polygons = contours
+        .iter()
+        .map(|contour| {
+            contour
+                .iter()
+                .map(|(from, ..)| Vec2::new(from.x, from.y))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut builder = Path::builder();
+    let mut curves = Vec::new();
+    for contour in &contours {
+        builder.begin(contour[0].0);
+        for &(from, ctrl, to) in contour {
+            let Some(ctrl) = ctrl else {
+                builder.line_to(to);
+                continue;
+            };
+            // Curves whose triangle reaches across the polygon are split until it doesn't
+            let mut pending = vec![(QuadraticBezierSegment { from, ctrl, to }, 0)];
+            while let Some((curve, splits)) = pending.pop() {
+                // A point just off the middle of the chord, towards the control point
+                let middle = curve.from.lerp(curve.to, 0.5);
+                let probe = middle + (curve.ctrl - middle) * 0.001;
+                let is_concave = is_inside(Vec2::new(probe.x, probe.y), &polygons);
+                let is_ctrl_inside = is_inside(Vec2::new(curve.ctrl.x, curve.ctrl.y), &polygons);
+                if is_ctrl_inside != is_concave && splits < MAX_CURVE_SPLITS {
+                    let (start, end) = curve.split(0.5);
+                    pending.push((end, splits + 1));
+                    pending.push((start, splits + 1));
+                    continue;
+                }
+                if is_concave {
+                    builder.line_to(curve.ctrl);
+                }
+                builder.line_to(curve.to);
+                curves.push((curve, is_concave));
+            }
+        }
+        builder.end(true);
+    }
+    if let Err(e) = fill_tess.tessellate_path(
+        &builder.build(),
+        &FillOptions::tolerance(tolerance),
+        &mut BuffersBuilder::new(
+            &mut buffer,
+            VertexConstructor {
+                color: path.color,
+                transform: path.abs_transform,
+                depth,
+            },
+        ),
+    ) {
+        error!("FillTessellator error: {:?}", e);
+    }
+
+    let constructor = VertexConstructor {
+        color: path.color,
+        transform: path.abs_transform,
+        depth,
+    };
+    for (curve, is_concave) in curves {
+        let side = if is_concave { -1.0 } else { 1.0 };
+        let offset = buffer.vertices.len() as IndexType;
+        for (point, [u, v]) in [
+            (curve.from, [0.0, 0.0]),
+            (curve.ctrl, [0.5, 0.0]),
+            (curve.to, [1.0, 1.0]),
+        ] {
+            let mut vertex = constructor.process_vertex(point);
+            vertex.curve = [u, v, side];
+            buffer.vertices.push(vertex);
+        }
+        buffer.indices.extend([offset, offset + 1, offset + 2]);
+    }
 
-    let mut color = None;
-    for path in &svg.paths {
-        let mut buffer = VertexBuffers::new();
+    buffer
+}
 
-        if color.is_none() {
-            color = Some(path.color);
+/// Splits a fill into closed contours of quadratic curves and lines, with cubic curves
+/// approximated by quadratic ones. Curves whose control point lies on their chord are lines.
+fn quadratic_contours(path: &PathDescriptor, tolerance: f32) -> Vec<Vec<QuadraticSegment>> {
+    let is_straight = |from: Point, ctrl: Point, to: Point| {
+        let (chord, arm) = (to - from, ctrl - from);
+        chord.cross(arm).abs() <= f32::EPSILON * chord.square_length().max(arm.square_length())
+    };
+    let mut contours = Vec::new();
+    let mut contour: Vec<QuadraticSegment> = Vec::new();
+    let (mut first, mut last) = (Point::zero(), Point::zero());
+    let push =
+        |contour: &mut Vec<QuadraticSegment>, from: Point, ctrl: Option<Point>, to: Point| {
+            if (to - from).square_length() > 0.0 {
+                let ctrl = ctrl.filter(|&ctrl| !is_straight(from, ctrl, to));
+                contour.push((from, ctrl, to));
+            }
+        };
+    // Only the end points of the events are relied on, as they are the only ones the
+    // tessellators use
+    for event in &path.segments {
+        match *event {
+            PathEvent::Begin { at } => {
+                contour.clear();
+                (first, last) = (at, at);
+            }
+            PathEvent::Line { to, .. } => {
+                push(&mut contour, last, None, to);
+                last = to;
+            }
+            PathEvent::Quadratic { ctrl, to, .. } => {
+                push(&mut contour, last, Some(ctrl), to);
+                last = to;
+            }
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                CubicBezierSegment {
+                    from: last,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                }
+                .for_each_quadratic_bezier(tolerance, &mut |quadratic| {
+                    push(
+                        &mut contour,
+                        quadratic.from,
+                        Some(quadratic.ctrl),
+                        quadratic.to,
+                    );
+                });
+                last = to;
+            }
+            // Fills are always closed
+            PathEvent::End { .. } => {
+                push(&mut contour, last, None, first);
+                if !contour.is_empty() {
+                    contours.push(std::mem::take(&mut contour));
+                }
+            }
         }
+    }
+    contours
+}
 
-        let segments = path.segments.clone();
-        match path.draw_type {
-            DrawType::Fill => {
-                if let Err(e) = fill_tess.tessellate(
-                    segments,
-                    &FillOptions::tolerance(0.001),
+/// Tessellates the anti-aliasing fringe along the edges of a fill or stroke. Strokes are fringed
+/// on both sides, fills only on the side of each contour that faces away from the fill, so that
+/// translucent fills don't get a darker band along their edges.
+fn generate_fringe_buffer(
+    path: &PathDescriptor,
+    depth: f32,
+    tolerance: Option<f32>,
+    stroke_tess: &mut StrokeTessellator,
+) -> VertexBuffers {
+    let mut buffer = VertexBuffers::new();
+    let constructor = |side, half_width| FringeVertexConstructor {
+        vertex: VertexConstructor {
+            color: path.color,
+            transform: path.abs_transform,
+            depth,
+        },
+        side,
+        half_width,
+    };
+
+    match path.draw_type {
+        DrawType::Fill => {
+            let tolerance = tolerance.unwrap_or(0.001);
+            let options = StrokeOptions::tolerance(tolerance).with_line_join(LineJoin::Miter);
+            let contours = quadratic_contours(path, tolerance);
+            let polygons = contours
+                .iter()
+                .map(|contour| flatten_contour(contour, tolerance))
+                .collect::<Vec<_>>();
+            for (contour, polygon) in contours.iter().zip(&polygons) {
+                // Fills are implicitly closed, so their fringe needs to be closed as well
+                let mut builder = Path::builder();
+                builder.begin(contour[0].0);
+                for &(_, ctrl, to) in contour {
+                    match ctrl {
+                        Some(ctrl) => builder.quadratic_bezier_to(ctrl, to),
+                        None => builder.line_to(to),
+                    };
+                }
+                builder.end(true);
+                if let Err(e) = stroke_tess.tessellate_path(
+                    &builder.build(),
+                    &options,
                     &mut BuffersBuilder::new(
                         &mut buffer,
-                        VertexConstructor {
-                            color: path.color,
-                            transform: path.abs_transform,
-                        },
+                        constructor(outward_side(polygon, &polygons), 0.0),
                     ),
                 ) {
-                    error!("FillTessellator error: {:?}", e);
+                    error!("StrokeTessellator error: {:?}", e);
                 }
             }
-            DrawType::Stroke(opts) => {
+        }
+        DrawType::Stroke(opts) => {
+            let options = tolerance.map_or(opts, |tolerance| opts.with_tolerance(tolerance));
+            for side in [Side::Positive, Side::Negative] {
                 if let Err(e) = stroke_tess.tessellate(
-                    segments,
-                    &opts,
-                    &mut BuffersBuilder::new(
-                        &mut buffer,
-                        VertexConstructor {
-                            color: path.color,
-                            transform: path.abs_transform,
-                        },
-                    ),
+                    path.segments.iter().copied(),
+                    &options,
+                    &mut BuffersBuilder::new(&mut buffer, constructor(side, opts.line_width / 2.0)),
                 ) {
                     error!("StrokeTessellator error: {:?}", e);
                 }
             }
         }
-        buffers.extend_one(buffer);
     }
-    debug!("Tessellating SVG: {} ... Done", svg.name);
 
-    buffers
+    buffer
+}
+
+/// Approximates a closed contour by a polygon, with its curves flattened.
+fn flatten_contour(contour: &[QuadraticSegment], tolerance: f32) -> Vec<Vec2> {
+    let mut polygon = Vec::new();
+    for &(from, ctrl, to) in contour {
+        match ctrl {
+            Some(ctrl) => polygon.extend(
+                QuadraticBezierSegment { from, ctrl, to }
+                    .flattened(tolerance)
+                    .map(|point| Vec2::new(point.x, point.y)),
+            ),
+            None => polygon.push(Vec2::new(to.x, to.y)),
+        }
+    }
+    polygon
+}
+
+/// The side of a contour that faces away from the fill of all `polygons`, checked next to the
+/// middle of its longest edge. Lyon's positive side is to the left of the direction of the
+/// contour, with the y-axis pointing up.
+fn outward_side(polygon: &[Vec2], polygons: &[Vec<Vec2>]) -> Side {
+    let edges = polygon.iter().zip(polygon.iter().cycle().skip(1));
+    let Some((&from, &to)) =
+        edges.max_by(|(a, b), (c, d)| a.distance_squared(**b).total_cmp(&c.distance_squared(**d)))
+    else {
+        return Side::Positive;
+    };
+    let probe = from.lerp(to, 0.5) + (to - from).perp() * 0.001;
+    if is_inside(probe, polygons) {
+        Side::Negative
+    } else {
+        Side::Positive
+    }
+}
+
+/// Lyon doesn't guarantee any winding order, especially for strokes, so all triangles are
+/// wound clockwise in SVG coordinates. Once the mesh is flipped into Bevy's coordinate system
+/// they are counter-clockwise and thereby front faces, which keeps back-face culling from
+/// hiding parts of the SVG. Anti-aliasing fringes are oriented as they are once expanded.
+fn orient_triangles(buffer: &mut VertexBuffers) {
+    let VertexBuffers { vertices, indices } = buffer;
+    for triangle in indices.chunks_exact_mut(3) {
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|idx| {
+            let vertex = vertices[idx as usize];
+            [
+                vertex.position[0] + vertex.fringe_offset[0],
+                vertex.position[1] + vertex.fringe_offset[1],
+            ]
+        });
+        let area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+        if area > 0.0 {
+            triangle.swap(1, 2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::vertex_buffer::Vertex;
+
+    #[test]
+    fn analytic_quadratic() {
+        let svg: Svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">
+            <path d="M 0 0 Q 5 10 10 0 L 10 -5 L 0 -5 Z"/>
+        </svg>"#
+            .parse()
+            .unwrap();
+        let settings = TessellationSettings {
+            curves: SvgCurves::Analytic,
+            ..Default::default()
+        };
+        let buffer = generate_path_buffer(
+            &svg.paths[0],
+            0.0,
+            &settings,
+            &mut FillTessellator::new(),
+            &mut StrokeTessellator::new(),
+        );
+
+        // The rectangle below the curve is filled by the tessellator, the curve is one triangle
+        let curve_vertices = buffer
+            .vertices
+            .iter()
+            .filter(|vertex| vertex.curve != Vertex::NO_CURVE)
+            .map(|vertex| (vertex.position, vertex.curve))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            curve_vertices,
+            [
+                ([0.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+                ([5.0, 10.0, 0.0], [0.5, 0.0, 1.0]),
+                ([10.0, 0.0, 0.0], [1.0, 1.0, 1.0]),
+            ]
+        );
+        assert!(buffer.indices.len() > 3);
+
+        // The middle of the curve at (5, 5) is at a quarter, a half and a quarter of the corners,
+        // and interpolates to a point on the curve
+        let weights = [0.25, 0.5, 0.25];
+        let [u, v] = [0, 1].map(|idx| {
+            curve_vertices
+                .iter()
+                .zip(weights)
+                .map(|((_, curve), weight)| curve[idx] * weight)
+                .sum::<f32>()
+        });
+        assert_eq!(u * u - v, 0.0);
+
+        // All triangles, including the one of the curve, are wound clockwise in SVG coordinates
+        for triangle in buffer.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|idx| buffer.vertices[triangle[idx] as usize].position);
+            let area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+            assert!(
+                area <= 0.0,
+                "triangle {triangle:?} is wound counter-clockwise"
+            );
+        }
+    }
 }
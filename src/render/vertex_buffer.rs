@@ -1,25 +1,81 @@
+#[cfg(feature = "2d")]
+use bevy::asset::{Assets, Handle};
 use bevy::{
     color::{Color, ColorToComponents},
+    math::{Rect, Vec2},
     render::{
-        mesh::{Indices, Mesh, VertexAttributeValues},
+        mesh::{Indices, Mesh, MeshVertexAttribute, VertexAttributeValues},
         render_asset::RenderAssetUsages,
-        render_resource::PrimitiveTopology,
+        render_resource::{PrimitiveTopology, VertexFormat},
     },
 };
 use copyless::VecHelper;
-use lyon_path::math::Point;
+use lyon_path::{
+    math::{Point, Vector},
+    Side,
+};
 use lyon_tessellation::{
     self, FillVertex, FillVertexConstructor, StrokeVertex, StrokeVertexConstructor,
 };
 
-use crate::Convert;
+use crate::{
+    svg::{Svg, SvgVertexAttributes},
+    Convert,
+};
 
 /// A vertex with all the necessary attributes to be inserted into a Bevy
 /// [`Mesh`](bevy::render::mesh::Mesh).
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vertex {
-    position: [f32; 3],
-    color: [f32; 4],
+    pub(crate) position: [f32; 3],
+    pub(crate) color: [f32; 4],
+    /// Offset of a stroke vertex from the center line of its stroke, zero for fills and strokes
+    /// that are scaled together with the SVG.
+    pub(crate) stroke_offset: [f32; 2],
+    /// Direction in which a vertex of an anti-aliasing fringe is moved by one pixel, zero for
+    /// all other vertices.
+    pub(crate) fringe_offset: [f32; 2],
+    /// Coordinates of the vertex in the triangle of a quadratic curve, see
+    /// [`Svg::ATTRIBUTE_CURVE`]. All other vertices are inside of their curve.
+    pub(crate) curve: [f32; 3],
+}
+
+impl Vertex {
+    /// Curve coordinates of a vertex that isn't part of a curve, which is always filled.
+    pub(crate) const NO_CURVE: [f32; 3] = [0.0, 1.0, 1.0];
+}
+
+impl Svg {
+    /// Offset of every vertex of a stroke from the center line of the stroke, in SVG units.
+    /// It is zero for the vertices of fills and of strokes that are scaled together with the SVG.
+    ///
+    /// Used to keep the stroke width constant on screen, see
+    /// [`StrokeScaling::ScreenSpace`](crate::prelude::StrokeScaling::ScreenSpace). Only generated
+    /// if any stroke keeps its width, including strokes with `vector-effect="non-scaling-stroke"`.
+    pub const ATTRIBUTE_STROKE_OFFSET: MeshVertexAttribute = MeshVertexAttribute::new(
+        "Vertex_StrokeOffset",
+        8_514_826_680,
+        VertexFormat::Float32x2,
+    );
+
+    /// Direction in which the outer vertices of the anti-aliasing fringe are moved, by one
+    /// physical pixel per unit. It is zero for all other vertices.
+    ///
+    /// Only generated with [`SvgAntiAliasing::Fringe`](crate::prelude::SvgAntiAliasing::Fringe).
+    pub const ATTRIBUTE_FRINGE_OFFSET: MeshVertexAttribute = MeshVertexAttribute::new(
+        "Vertex_FringeOffset",
+        8_514_826_681,
+        VertexFormat::Float32x2,
+    );
+
+    /// Coordinates `(u, v, side)` of the vertices of the triangles that render quadratic curves,
+    /// spanned by the start, control and end point of the curve. The curve is where
+    /// `u² - v = 0`, and the side that is filled is where `side * (u² - v) < 0`. All other
+    /// vertices are inside, at `(0, 1, 1)`.
+    ///
+    /// Only generated with [`SvgCurves::Analytic`](crate::prelude::SvgCurves::Analytic).
+    pub const ATTRIBUTE_CURVE: MeshVertexAttribute =
+        MeshVertexAttribute::new("Vertex_Curve", 8_514_826_682, VertexFormat::Float32x3);
 }
 
 /// The index type of a Bevy [`Mesh`](bevy::render::mesh::Mesh).
@@ -28,6 +84,80 @@ pub type IndexType = u32;
 /// Lyon's [`VertexBuffers`] generic data type defined for [`Vertex`].
 pub type VertexBuffers = lyon_tessellation::VertexBuffers<Vertex, IndexType>;
 
+/// Moves the vertices of a mesh tessellated from an [`Svg`], with a function that maps points
+/// in SVG coordinates, i.e. with the y-axis pointing down. The function needs to map both axes
+/// independently, like scaling, stretching or mirroring does.
+///
+/// Stroke offsets are mapped together with the center line of their stroke, and anti-aliasing
+/// fringes keep facing away from their edge.
+#[cfg(feature = "2d")]
+pub(crate) fn map_mesh_vertices(mesh: &mut Mesh, map: impl Fn(Vec2) -> Vec2) {
+    // The mesh is flipped vertically
+    let map = |point: Vec2| {
+        let point = map(Vec2::new(point.x, -point.y));
+        Vec2::new(point.x, -point.y)
+    };
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return;
+    };
+    let original = positions
+        .iter()
+        .map(|position| Vec2::new(position[0], position[1]))
+        .collect::<Vec<_>>();
+    for (position, point) in positions.iter_mut().zip(&original) {
+        let point = map(*point);
+        position[0] = point.x;
+        position[1] = point.y;
+    }
+    if let Some(VertexAttributeValues::Float32x2(offsets)) =
+        mesh.attribute_mut(Svg::ATTRIBUTE_STROKE_OFFSET)
+    {
+        for (offset, point) in offsets.iter_mut().zip(&original) {
+            let center = *point - Vec2::from(*offset);
+            *offset = (map(*point) - map(center)).to_array();
+        }
+    }
+    if let Some(VertexAttributeValues::Float32x2(offsets)) =
+        mesh.attribute_mut(Svg::ATTRIBUTE_FRINGE_OFFSET)
+    {
+        for (offset, point) in offsets.iter_mut().zip(&original) {
+            let offset_vec = Vec2::from(*offset);
+            // Normals are scaled inversely to the axes they are perpendicular to
+            let epsilon = 0.001 * (1.0 + point.abs().max_element());
+            let scale = (map(*point + epsilon) - map(*point)) / epsilon;
+            if scale.x == 0.0 || scale.y == 0.0 {
+                continue;
+            }
+            *offset = ((offset_vec / scale).normalize_or_zero() * offset_vec.length()).to_array();
+        }
+    }
+}
+
+/// Replaces the mesh behind `mapped` with a copy of the mesh of the [`Svg`], with its vertices
+/// moved by [`map_mesh_vertices`], or adds it if there isn't one yet.
+///
+/// Returns `false` if the mesh of the [`Svg`] isn't loaded.
+#[cfg(feature = "2d")]
+pub(crate) fn map_svg_mesh(
+    meshes: &mut Assets<Mesh>,
+    svg: &Svg,
+    mapped: &mut Handle<Mesh>,
+    map: impl Fn(Vec2) -> Vec2,
+) -> bool {
+    let Some(mut mesh) = meshes.get(&svg.mesh).cloned() else {
+        return false;
+    };
+    map_mesh_vertices(&mut mesh, map);
+    if let Some(previous) = meshes.get_mut(mapped.id()) {
+        *previous = mesh;
+    } else {
+        *mapped = meshes.add(mesh);
+    }
+    true
+}
+
 #[allow(clippy::single_call_fn)]
 fn flip_mesh_vertically(mesh: &mut Mesh) {
     if let Some(VertexAttributeValues::Float32x3(positions)) =
@@ -38,25 +168,88 @@ fn flip_mesh_vertically(mesh: &mut Mesh) {
             position[1] = -position[1];
         }
     }
+    for attribute in [Svg::ATTRIBUTE_STROKE_OFFSET, Svg::ATTRIBUTE_FRINGE_OFFSET] {
+        if let Some(VertexAttributeValues::Float32x2(offsets)) = mesh.attribute_mut(attribute) {
+            for offset in offsets.iter_mut() {
+                offset[1] = -offset[1];
+            }
+        }
+    }
 }
 
+/// Converts the buffers into a [`Mesh`] with all vertex attributes, with the UVs mapped across the
+/// bounds of the vertices.
 impl Convert<Mesh> for VertexBuffers {
     fn convert(self) -> Mesh {
-        let mut positions = Vec::with_capacity(self.vertices.len());
-        let mut colors = Vec::with_capacity(self.vertices.len());
+        let bounds = self
+            .vertices
+            .iter()
+            .fold(None, |bounds: Option<Rect>, vert| {
+                let point = Vec2::new(vert.position[0], vert.position[1]);
+                Some(
+                    bounds.map_or(Rect::from_center_size(point, Vec2::ZERO), |bounds| {
+                        bounds.union_point(point)
+                    }),
+                )
+            })
+            .unwrap_or_default();
+        (self, bounds, SvgVertexAttributes::Standard).convert()
+    }
+}
+
+/// Converts the buffers into a [`Mesh`] with the given vertex attributes, with the UVs mapped
+/// across the given bounds in SVG coordinates.
+impl Convert<Mesh> for (VertexBuffers, Rect, SvgVertexAttributes) {
+    fn convert(self) -> Mesh {
+        let (buffers, bounds, attributes) = self;
+        let uv_scale = Vec2::ONE / bounds.size().max(Vec2::splat(f32::EPSILON));
+
+        let mut positions = Vec::with_capacity(buffers.vertices.len());
+        let mut colors = Vec::with_capacity(buffers.vertices.len());
+        let mut uvs = Vec::with_capacity(buffers.vertices.len());
+        let mut stroke_offsets = Vec::with_capacity(buffers.vertices.len());
+        let mut fringe_offsets = Vec::with_capacity(buffers.vertices.len());
+        let mut curves = Vec::with_capacity(buffers.vertices.len());
 
-        for vert in self.vertices {
+        for vert in buffers.vertices {
+            let uv = (Vec2::new(vert.position[0], vert.position[1]) - bounds.min) * uv_scale;
             positions.alloc().init(vert.position);
             colors.alloc().init(vert.color);
+            uvs.alloc().init(uv.to_array());
+            stroke_offsets.alloc().init(vert.stroke_offset);
+            fringe_offsets.alloc().init(vert.fringe_offset);
+            curves.alloc().init(vert.curve);
         }
+        let has_curves = curves.iter().any(|curve| *curve != Vertex::NO_CURVE);
 
         let mut mesh = Mesh::new(
             PrimitiveTopology::TriangleList,
             RenderAssetUsages::default(),
         );
         mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        if attributes == SvgVertexAttributes::Standard {
+            // SVGs are flat, so every vertex faces the viewer
+            let normals = vec![[0.0, 0.0, 1.0]; uvs.len()];
+            let tangents = vec![[1.0, 0.0, 0.0, 1.0]; uvs.len()];
+            mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+            mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, tangents);
+        }
+        // In 3D the curve coordinates are passed on in place of the UVs, which only exist if the
+        // mesh has them
+        if attributes == SvgVertexAttributes::Standard || has_curves {
+            mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        }
         mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
-        mesh.insert_indices(Indices::U32(self.indices));
+        if stroke_offsets.iter().any(|offset| *offset != [0.0; 2]) {
+            mesh.insert_attribute(Svg::ATTRIBUTE_STROKE_OFFSET, stroke_offsets);
+        }
+        if fringe_offsets.iter().any(|offset| *offset != [0.0; 2]) {
+            mesh.insert_attribute(Svg::ATTRIBUTE_FRINGE_OFFSET, fringe_offsets);
+        }
+        if has_curves {
+            mesh.insert_attribute(Svg::ATTRIBUTE_CURVE, curves);
+        }
+        mesh.insert_indices(Indices::U32(buffers.indices));
 
         // Bevy has a different y-axis origin, so we need to flip that axis
         flip_mesh_vertically(&mut mesh);
@@ -69,18 +262,32 @@ impl Convert<Mesh> for VertexBuffers {
 pub struct VertexConstructor {
     pub(crate) color: Color,
     pub(crate) transform: usvg::Transform,
+    pub(crate) depth: f32,
 }
 
 impl VertexConstructor {
-    fn process_vertex(&self, point: Point) -> Vertex {
-        let pos = {
-            let mut point = usvg::tiny_skia_path::Point::from_xy(point.x, point.y);
-            self.transform.map_point(&mut point);
-            Point::new(point.x, point.y)
-        };
+    fn transform_point(&self, point: Point) -> Point {
+        let mut point = usvg::tiny_skia_path::Point::from_xy(point.x, point.y);
+        self.transform.map_point(&mut point);
+        Point::new(point.x, point.y)
+    }
+
+    fn transform_vector(&self, vector: Vector) -> Vector {
+        let transform = self.transform;
+        Vector::new(
+            transform.sx * vector.x + transform.kx * vector.y,
+            transform.ky * vector.x + transform.sy * vector.y,
+        )
+    }
+
+    pub(crate) fn process_vertex(&self, point: Point) -> Vertex {
+        let pos = self.transform_point(point);
         Vertex {
-            position: [pos.x, pos.y, 0.0],
+            position: [pos.x, pos.y, self.depth],
             color: self.color.to_linear().to_f32_array(),
+            stroke_offset: [0.0; 2],
+            fringe_offset: [0.0; 2],
+            curve: Vertex::NO_CURVE,
         }
     }
 }
@@ -95,13 +302,53 @@ impl FillVertexConstructor<Vertex> for VertexConstructor {
 /// Enables the construction of a [`Vertex`] when using a `StrokeTessellator`.
 impl StrokeVertexConstructor<Vertex> for VertexConstructor {
     fn new_vertex(&mut self, vertex: StrokeVertex) -> Vertex {
-        self.process_vertex(vertex.position())
+        let mut vert = self.process_vertex(vertex.position());
+        let center = self.transform_point(vertex.position_on_path());
+        vert.stroke_offset = [vert.position[0] - center.x, vert.position[1] - center.y];
+        vert
+    }
+}
+
+/// Constructs the anti-aliasing fringe along one side of a path, from the vertices of a stroke
+/// along the path.
+///
+/// The fringe starts at the edge of the path on that side, with the color of the path, and fades
+/// out over one pixel away from the path. The edge of a fill is the path itself, so it has a
+/// `half_width` of zero and is only fringed on the side facing away from the fill, as a fringe
+/// on the inside would be drawn on top of the fill.
+pub struct FringeVertexConstructor {
+    pub(crate) vertex: VertexConstructor,
+    pub(crate) side: Side,
+    pub(crate) half_width: f32,
+}
+
+impl StrokeVertexConstructor<Vertex> for FringeVertexConstructor {
+    fn new_vertex(&mut self, vertex: StrokeVertex) -> Vertex {
+        let normal = vertex.normal();
+        let is_outer = vertex.side() == self.side;
+        // The vertices of the other side are moved onto the edge of this side
+        let edge = if is_outer {
+            vertex.position_on_path() + normal * self.half_width
+        } else {
+            vertex.position_on_path() - normal * self.half_width
+        };
+        let mut vert = self.vertex.process_vertex(edge);
+        let center = self.vertex.transform_point(vertex.position_on_path());
+        vert.stroke_offset = [vert.position[0] - center.x, vert.position[1] - center.y];
+        if is_outer {
+            vert.color[3] = 0.0;
+            // Only the direction is transformed, the normal is longer than one at miter joins
+            let direction = self.vertex.transform_vector(normal).normalize() * normal.length();
+            if direction.x.is_finite() && direction.y.is_finite() {
+                vert.fringe_offset = direction.to_array();
+            }
+        }
+        vert
     }
 }
 
 pub trait BufferExt<A> {
     fn extend_one(&mut self, item: A);
-    #[allow(dead_code)]
     fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T);
 }
 
@@ -122,12 +369,9 @@ impl BufferExt<VertexBuffers> for VertexBuffers {
 
         for buf in iter {
             let num_verts = buf.vertices.len() as u32;
-            for vert in buf.vertices {
-                self.vertices.alloc().init(vert);
-            }
-            for idx in buf.indices {
-                self.indices.alloc().init(idx + offset);
-            }
+            self.vertices.extend(buf.vertices);
+            self.indices
+                .extend(buf.indices.into_iter().map(|idx| idx + offset));
             offset += num_verts;
         }
     }
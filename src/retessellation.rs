@@ -0,0 +1,122 @@
+//! Retessellation of [`Svg`]s that are edited at runtime, on the [`AsyncComputeTaskPool`].
+
+use crate::{render::tessellation, svg::Svg};
+use bevy::{
+    asset::{AssetEvent, AssetId, Assets, Handle},
+    ecs::{
+        event::EventReader,
+        system::{Res, ResMut, Resource},
+    },
+    log::debug,
+    render::mesh::Mesh,
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+    utils::HashMap,
+};
+
+/// Tessellations running on the [`AsyncComputeTaskPool`], by the mesh they are tessellated into.
+#[derive(Debug, Default, Resource)]
+pub struct SvgTessellationTasks(HashMap<AssetId<Mesh>, (Handle<Mesh>, Task<Mesh>)>);
+
+impl SvgTessellationTasks {
+    /// Tessellates the SVG in the background into the mesh of `handle`, with the given tolerance
+    /// or the ones of its paths. A tessellation into the same mesh that is still running is
+    /// dropped.
+    ///
+    /// The paths of the SVG are shared with the task, so cloning an SVG for it is cheap.
+    pub(crate) fn spawn(&mut self, handle: Handle<Mesh>, svg: Svg, tolerance: Option<f32>) {
+        let task = AsyncComputeTaskPool::get().spawn(async move {
+            debug!("Tessellating SVG `{}` in the background ...", svg.name);
+            let mesh = tessellation::with_thread_tessellators(|fill_tess, stroke_tess| {
+                svg.tessellate_with(tolerance, fill_tess, stroke_tess)
+            });
+            debug!("Tessellating SVG `{}` in the background ... Done", svg.name);
+            mesh
+        });
+        self.0.insert(handle.id(), (handle, task));
+    }
+}
+
+/// Puts the meshes of finished tessellations into their handles, which replaces the previous
+/// meshes for all entities showing them at once.
+pub fn finish_tessellations(
+    mut tasks: ResMut<SvgTessellationTasks>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    tasks.0.retain(|_, (handle, task)| {
+        let Some(mesh) = block_on(future::poll_once(task)) else {
+            return true;
+        };
+        meshes.insert(&*handle, mesh);
+        false
+    });
+}
+
+/// Retessellates the mesh of every [`Svg`] whose paths, or settings that change its mesh, were
+/// edited through [`Assets::get_mut`].
+///
+/// The mesh is tessellated in the background, and the previous mesh stays visible until the
+/// new one replaces it under the same handle. [`Svg`]s without a mesh, like the ones built with
+/// [`SvgBuilder::build`](crate::prelude::SvgBuilder::build), get one when they are added. Edited
+/// paths no longer match the document of the SVG, so it is dropped, and rasterizing the SVG
+/// renders its paths instead.
+pub fn retessellate_modified(
+    mut svg_events: EventReader<AssetEvent<Svg>>,
+    mut svgs: ResMut<Assets<Svg>>,
+    meshes: Res<Assets<Mesh>>,
+    mut tasks: ResMut<SvgTessellationTasks>,
+) {
+    // An SVG can be modified several times in a frame, but only needs to be hashed once
+    let mut events = HashMap::<AssetId<Svg>, bool>::default();
+    for event in svg_events.read() {
+        match event {
+            AssetEvent::Added { id } | AssetEvent::LoadedWithDependencies { id } => {
+                events.entry(*id).or_default();
+            }
+            AssetEvent::Modified { id } => {
+                events.insert(*id, true);
+            }
+            AssetEvent::Removed { id } => {
+                events.remove(id);
+            }
+            AssetEvent::Unused { .. } => {}
+        }
+    }
+
+    for (id, is_modified) in events {
+        let Some(svg) = svgs.get(id) else {
+            continue;
+        };
+        let has_mesh = svg.mesh != Handle::default()
+            && (meshes.contains(&svg.mesh) || tasks.0.contains_key(&svg.mesh.id()));
+        // Loaded SVGs come with their mesh, so only edits after that are retessellated
+        if has_mesh && !is_modified {
+            continue;
+        }
+
+        // This also skips the modifications of the SVG below, which store the new hash
+        let hash = svg.tessellation_hash();
+        let previous_hash = svg.tessellated_from;
+        if has_mesh && previous_hash == Some(hash) {
+            continue;
+        }
+
+        let mesh = if has_mesh {
+            debug!("Svg `{}` edited. Retessellating it.", svg.name);
+            svg.mesh.clone()
+        } else {
+            debug!("Svg `{}` added without a mesh. Tessellating it.", svg.name);
+            meshes.reserve_handle()
+        };
+        tasks.spawn(mesh.clone(), svg.clone(), None);
+
+        let Some(svg) = svgs.get_mut(id) else {
+            continue;
+        };
+        svg.mesh = mesh;
+        svg.tessellated_from = Some(hash);
+        #[cfg(feature = "rasterize")]
+        if previous_hash.is_some_and(|previous_hash| previous_hash.paths != hash.paths) {
+            svg.tree = None;
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Nine-slice scaling, which stretches SVGs without distorting their corners.
+
+use bevy::{ecs::component::Component, math::Vec2};
+
+#[cfg(feature = "2d")]
+use crate::{
+    mapped_mesh::{SvgMappedMesh, SvgMeshMapping},
+    svg::Svg,
+};
+
+/// Stretches an `Svg2d` or `SvgNode` to a different size, while its corners keep their size.
+///
+/// The insets, in SVG units, divide the SVG into nine regions. The corners are not scaled, the
+/// edges are only stretched along their edge and the center is stretched in both directions.
+/// For a three-slice, set the insets of one axis to zero. If the size is smaller than the
+/// corners, the corners are scaled down to fit.
+///
+/// An `Svg2d` is stretched to [`size`](SvgSlices::size) by moving the vertices of its mesh,
+/// so an `SvgLod` or `SvgFit` on the same entity is ignored. An `SvgNode` is stretched to the
+/// size of its node instead.
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "2d", require(SvgMappedMesh<SvgSlices>))]
+pub struct SvgSlices {
+    /// Width of the left edge.
+    pub left: f32,
+    /// Width of the right edge.
+    pub right: f32,
+    /// Height of the top edge.
+    pub top: f32,
+    /// Height of the bottom edge.
+    pub bottom: f32,
+    /// Size the `Svg2d` is stretched to, in SVG units.
+    pub size: Vec2,
+}
+
+impl SvgSlices {
+    /// Nine-slice with the same inset on all sides.
+    pub fn uniform(inset: f32, size: Vec2) -> Self {
+        Self {
+            left: inset,
+            right: inset,
+            top: inset,
+            bottom: inset,
+            size,
+        }
+    }
+
+    /// Three-slice that is stretched horizontally, with the given insets on the left and right.
+    pub fn horizontal(left: f32, right: f32, width: f32, height: f32) -> Self {
+        Self {
+            left,
+            right,
+            top: 0.0,
+            bottom: 0.0,
+            size: Vec2::new(width, height),
+        }
+    }
+
+    /// Three-slice that is stretched vertically, with the given insets on the top and bottom.
+    pub fn vertical(top: f32, bottom: f32, width: f32, height: f32) -> Self {
+        Self {
+            left: 0.0,
+            right: 0.0,
+            top,
+            bottom,
+            size: Vec2::new(width, height),
+        }
+    }
+
+    /// Maps a point of an SVG with the given size, in SVG coordinates, to its position in the
+    /// stretched SVG.
+    pub fn map_point(&self, point: Vec2, svg_size: Vec2) -> Vec2 {
+        Vec2::new(
+            map_axis(point.x, svg_size.x, self.left, self.right, self.size.x),
+            map_axis(point.y, svg_size.y, self.top, self.bottom, self.size.y),
+        )
+    }
+}
+
+/// Maps a coordinate along an axis of the given length to the target length, so that the
+/// start and end insets keep their length.
+fn map_axis(value: f32, length: f32, start: f32, end: f32, target: f32) -> f32 {
+    let corners = start + end;
+    // Corners that don't fit into the target are scaled down
+    let corner_scale = if corners > target && corners > 0.0 {
+        target / corners
+    } else {
+        1.0
+    };
+    if value <= start {
+        value * corner_scale
+    } else if value >= length - end {
+        target - (length - value) * corner_scale
+    } else {
+        let middle = length - corners;
+        let target_middle = target - corners * corner_scale;
+        start * corner_scale + (value - start) * target_middle / middle
+    }
+}
+
+#[cfg(feature = "2d")]
+impl SvgMeshMapping for SvgSlices {
+    type Filter = ();
+
+    fn map_mesh_point(&self, point: Vec2, svg: &Svg) -> Vec2 {
+        self.map_point(point, svg.size)
+    }
+}
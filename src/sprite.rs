@@ -0,0 +1,139 @@
+//! Sprite-like SVGs, with the same options as a `Sprite`.
+
+use bevy::{
+    asset::{AssetEvent, Assets, Handle},
+    ecs::{
+        change_detection::{DetectChanges, DetectChangesMut, Ref},
+        component::{Component, ComponentId},
+        entity::Entity,
+        event::EventReader,
+        query::Has,
+        system::{Commands, Query, Res},
+        world::DeferredWorld,
+    },
+    math::Vec2,
+    sprite::Anchor,
+};
+
+use crate::{
+    fit::{SvgFit, SvgFitMode},
+    mapped_mesh::{modified_svgs, SvgMappedMesh},
+    origin::Origin,
+    render::Svg2d,
+    svg::Svg,
+};
+
+/// Displays an [`Svg`] like a `Sprite`, with the same options, so a raster sprite can be
+/// replaced by an SVG by only changing `Sprite { image, .. }` into `SvgSprite { svg, .. }`.
+///
+/// The entity is an `Svg2d`, whose handle is kept in sync with [`svg`](SvgSprite::svg). The
+/// [`anchor`](SvgSprite::anchor) sets its [`Origin`], and a custom size or flip is applied with
+/// an [`SvgFit`] that stretches the SVG. Without either, an [`SvgFit`] inserted by the user is
+/// left untouched.
+#[derive(Clone, Component, Debug, Default)]
+#[require(Svg2d)]
+#[component(on_insert = svg_sprite_on_insert)]
+pub struct SvgSprite {
+    /// The SVG to display.
+    pub svg: Handle<Svg>,
+    /// Size the SVG is stretched to, in world units, instead of the size of the SVG.
+    pub custom_size: Option<Vec2>,
+    /// Mirrors the SVG horizontally.
+    pub flip_x: bool,
+    /// Mirrors the SVG vertically.
+    pub flip_y: bool,
+    /// Point of the SVG at the translation of the entity.
+    pub anchor: Anchor,
+}
+
+impl SvgSprite {
+    /// Displays the SVG at its own size, anchored at its center.
+    pub fn from_svg(svg: Handle<Svg>) -> Self {
+        Self {
+            svg,
+            ..Default::default()
+        }
+    }
+
+    /// Displays the SVG stretched to the given size, anchored at its center.
+    pub fn sized(svg: Handle<Svg>, custom_size: Vec2) -> Self {
+        Self {
+            svg,
+            custom_size: Some(custom_size),
+            ..Default::default()
+        }
+    }
+
+    /// The [`SvgFit`] that stretches and flips the SVG, or `None` if it is shown as it is.
+    fn fit(&self, svg: &Svg) -> Option<SvgFit> {
+        if self.custom_size.is_none() && !self.flip_x && !self.flip_y {
+            return None;
+        }
+        Some(SvgFit {
+            size: self.custom_size.unwrap_or(svg.size),
+            mode: SvgFitMode::Stretch,
+            flip_x: self.flip_x,
+            flip_y: self.flip_y,
+        })
+    }
+}
+
+/// Marks an [`SvgFit`] that was inserted by the [`SvgSprite`] of the entity, so that it can be
+/// removed again without touching an [`SvgFit`] that was inserted by the user.
+#[derive(Clone, Component, Copy, Debug, Default)]
+pub struct SvgSpriteFit;
+
+fn svg_sprite_on_insert(mut world: DeferredWorld, entity: Entity, _component_id: ComponentId) {
+    let sprite = world.entity(entity).get::<SvgSprite>().unwrap();
+    let (handle, origin) = (sprite.svg.clone(), Origin::from(sprite.anchor));
+    let mut commands = world.commands();
+    commands.entity(entity).insert((Svg2d(handle), origin));
+}
+
+/// Applies the changes of every [`SvgSprite`] to its `Svg2d`, [`Origin`] and [`SvgFit`].
+pub fn apply_svg_sprites(
+    mut commands: Commands,
+    mut svg_events: EventReader<AssetEvent<Svg>>,
+    svgs: Res<Assets<Svg>>,
+    mut query: Query<(
+        Entity,
+        Ref<SvgSprite>,
+        &mut Svg2d,
+        &mut Origin,
+        Option<&SvgFit>,
+        Has<SvgSpriteFit>,
+    )>,
+) {
+    let modified = modified_svgs(&mut svg_events);
+
+    for (entity, sprite, mut svg_2d, mut origin, fit, is_sprite_fit) in &mut query {
+        if !(sprite.is_changed() || modified.contains(&sprite.svg.id())) {
+            continue;
+        }
+        if svg_2d.0 != sprite.svg {
+            // Reinserted, so its material is replaced as well
+            commands.entity(entity).insert(Svg2d(sprite.svg.clone()));
+        }
+        let anchor_origin = Origin::from(sprite.anchor);
+        if *origin != anchor_origin {
+            *origin = anchor_origin;
+        }
+
+        let Some(svg) = svgs.get(&sprite.svg) else {
+            continue;
+        };
+        match (sprite.fit(svg), fit) {
+            (Some(sprite_fit), fit) if fit != Some(&sprite_fit) => {
+                commands.entity(entity).insert((sprite_fit, SvgSpriteFit));
+            }
+            (None, Some(_)) if is_sprite_fit => {
+                commands
+                    .entity(entity)
+                    .remove::<(SvgFit, SvgMappedMesh<SvgFit>, SvgSpriteFit)>();
+                // Back to the mesh of the SVG, or of its `SvgLod`, which the mesh linker assigns
+                svg_2d.set_changed();
+            }
+            _ => (),
+        }
+    }
+}
@@ -1,19 +1,33 @@
 use bevy::{
-    asset::{Asset, Handle},
-    color::Color,
+    asset::{Asset, Assets, Handle},
+    color::{Alpha, Color, ColorToComponents},
+    ecs::system::Resource,
     log::{debug, trace, warn},
-    math::Vec2,
+    math::{Rect, Vec2},
     reflect::{std_traits::ReflectDefault, Reflect},
-    render::{mesh::Mesh, render_resource::AsBindGroup},
+    render::{
+        alpha::AlphaMode,
+        mesh::Mesh,
+        render_asset::RenderAssets,
+        render_resource::{AsBindGroup, AsBindGroupShaderType},
+        texture::GpuImage,
+    },
 };
 use copyless::VecHelper;
 use lyon_path::PathEvent;
-use lyon_tessellation::{math::Point, FillTessellator, StrokeTessellator};
-use std::collections::VecDeque;
+use lyon_tessellation::{
+    math::{Point, Transform},
+    FillTessellator, StrokeTessellator,
+};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::iter::Peekable;
 use std::path::PathBuf;
-use std::sync::Arc;
-use svgtypes::ViewBox;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use svgtypes::{AspectRatio, ViewBox};
 use usvg::{
     tiny_skia_path::{PathSegment, PathSegmentsIter},
     PaintOrder,
@@ -24,19 +38,242 @@ use crate::{loader::FileSvgError, render::tessellation, Convert};
 /// A loaded and deserialized SVG file.
 #[derive(AsBindGroup, Reflect, Debug, Clone, Asset)]
 #[reflect(Default, Debug)]
+#[uniform(0, SvgMaterialUniform)]
+#[bind_group_data(SvgMaterialKey)]
 pub struct Svg {
     /// The name of the file.
     pub name: String,
     /// Size of the SVG.
     pub size: Vec2,
     #[reflect(ignore)]
-    /// ViewBox of the SVG.
+    /// ViewBox of the SVG, as given by its `viewBox` attribute.
     pub view_box: ViewBox,
     #[reflect(ignore)]
-    /// All paths that make up the SVG.
-    pub paths: Vec<PathDescriptor>,
+    /// The `preserveAspectRatio` attribute of the SVG.
+    pub preserve_aspect_ratio: AspectRatio,
+    /// Bounding box of all the content of the SVG, in SVG coordinates.
+    pub content_bounds: Rect,
+    #[reflect(ignore)]
+    /// All paths that make up the SVG. When they are edited through `Assets::get_mut`, the
+    /// [`mesh`](Svg::mesh) is retessellated.
+    ///
+    /// They are shared with the tessellations running in the background, edit them with
+    /// [`Arc::make_mut`], which only copies them while a tessellation is still running.
+    pub paths: Arc<Vec<PathDescriptor>>,
     /// The fully tessellated paths as [`Mesh`].
     pub mesh: Handle<Mesh>,
+    /// How the alpha channel of the colors is handled when rendering the SVG.
+    ///
+    /// Defaults to [`AlphaMode::Blend`] if any of the paths is translucent, otherwise to [`AlphaMode::Opaque`].
+    pub alpha_mode: AlphaMode,
+    /// Which sides of the SVG are visible when it is rendered in 3D.
+    pub sides: SvgSides,
+    /// Distance along the z-axis between consecutive paths, in SVG units.
+    ///
+    /// All paths lie in the same plane by default, so in 3D overlapping paths z-fight instead of
+    /// being drawn in the order of the SVG. A small offset moves every path slightly in front of
+    /// the previous one. Only the z-axis of the [`Transform`](bevy::transform::components::Transform)
+    /// scales it, which is usually not scaled down together with the SVG.
+    ///
+    /// This is applied when the SVG is tessellated, e.g. set it with
+    /// [`SvgLoaderSettings::depth_offset`](crate::prelude::SvgLoaderSettings::depth_offset).
+    pub depth_offset: f32,
+    /// How the width of the strokes changes when the SVG is scaled or zoomed. Strokes with
+    /// `vector-effect="non-scaling-stroke"` always keep their width on screen, if the element, or
+    /// the `<use>` that draws it, has an id.
+    ///
+    /// This is applied when the SVG is tessellated, e.g. set it with
+    /// [`SvgLoaderSettings::stroke_scaling`](crate::prelude::SvgLoaderSettings::stroke_scaling).
+    pub stroke_scaling: StrokeScaling,
+    /// How the edges of the SVG are anti-aliased.
+    ///
+    /// This is applied when the SVG is tessellated, e.g. set it with
+    /// [`SvgLoaderSettings::anti_aliasing`](crate::prelude::SvgLoaderSettings::anti_aliasing).
+    pub anti_aliasing: SvgAntiAliasing,
+    /// How the curves of the fills are rendered.
+    ///
+    /// This is applied when the SVG is tessellated, e.g. set it with
+    /// [`SvgLoaderSettings::curves`](crate::prelude::SvgLoaderSettings::curves).
+    pub curves: SvgCurves,
+    /// Which vertex attributes the mesh gets besides the ones the [`Svg`] material renders with.
+    ///
+    /// This is applied when the SVG is tessellated, e.g. set it with
+    /// [`SvgLoaderSettings::vertex_attributes`](crate::prelude::SvgLoaderSettings::vertex_attributes).
+    pub vertex_attributes: SvgVertexAttributes,
+    #[cfg(feature = "rasterize")]
+    #[reflect(ignore)]
+    /// The parsed SVG document, which is rasterized by [`Svg::rasterize`]. SVGs that are loaded
+    /// pre-tessellated, or whose paths were edited, don't have it.
+    pub(crate) tree: Option<Arc<usvg::Tree>>,
+    #[reflect(ignore)]
+    /// What the mesh was last tessellated from, if the SVG was tessellated when it was loaded or
+    /// since it was edited.
+    pub(crate) tessellated_from: Option<TessellationHash>,
+}
+
+/// Hashes of everything the mesh of an [`Svg`] is tessellated from, to tell edits of an [`Svg`]
+/// apart from other modifications, like its alpha mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TessellationHash {
+    /// Hash of the paths, which are rasterized from the document of the SVG as well.
+    pub(crate) paths: u64,
+    /// Hash of the size and the settings that change the mesh.
+    pub(crate) settings: u64,
+}
+
+/// Which sides of an [`Svg`] are visible when it is rendered with an `Svg3d`.
+///
+/// 2D rendering doesn't cull any faces, so this has no effect on an `Svg2d`. An `Svg3d` rendered
+/// with an `Svg3dStandardMaterial` uses the `cull_mode` of the `StandardMaterial` instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug)]
+pub enum SvgSides {
+    /// Only the front of the SVG is visible, it disappears when viewed from behind.
+    #[default]
+    Front,
+    /// The SVG is visible from both sides, viewed from behind it appears mirrored.
+    DoubleSided,
+    /// The SVG is visible from both sides, viewed from behind it is mirrored back horizontally,
+    /// around the center of its content, so it looks the same as from the front and covers the
+    /// same area. Its depth and shadows are mirrored as well. Useful for signs and cards.
+    MirroredBack,
+}
+
+/// How the width of the strokes of an [`Svg`] changes when it is scaled or zoomed.
+///
+/// Insert it as a resource to override [`Svg::stroke_scaling`] of all SVGs.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect, Resource, Serialize, Deserialize,
+)]
+#[reflect(Default, Debug)]
+pub enum StrokeScaling {
+    /// Strokes are scaled together with the rest of the SVG.
+    #[default]
+    Scaled,
+    /// Strokes keep a constant width in physical pixels, no matter how the SVG is scaled or how far
+    /// the camera is zoomed. The width is the one the stroke has when the SVG is displayed at its
+    /// size, so a stroke of 2 SVG units is 2 pixels wide.
+    ///
+    /// The strokes are expanded in the vertex shader, which is only done for the [`Svg`] material.
+    /// Strokes with `vector-effect="non-scaling-stroke"` are always expanded like this.
+    ScreenSpace,
+}
+
+/// How the edges of an [`Svg`] are anti-aliased.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Default, Debug)]
+pub enum SvgAntiAliasing {
+    /// No anti-aliasing of its own, the edges are only smooth with MSAA.
+    #[default]
+    None,
+    /// Fills and strokes get a fringe, that fades out over one physical pixel along their edges.
+    /// This keeps the edges smooth without MSAA, e.g. on WebGL and mobile targets.
+    ///
+    /// The fringe needs blending, so an SVG with [`AlphaMode::Opaque`] is blended instead, and
+    /// with [`AlphaMode::Mask`] the fringe is cut off. It is expanded in the vertex shader, which
+    /// is only done for the [`Svg`] material.
+    Fringe,
+}
+
+/// How the curves of the fills of an [`Svg`] are rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Default, Debug)]
+pub enum SvgCurves {
+    /// Curves are flattened into line segments when the SVG is tessellated, following the
+    /// tolerance of the fill.
+    #[default]
+    Flattened,
+    /// Quadratic curves are kept as a single triangle each, whose coverage is computed in the
+    /// fragment shader, so they stay smooth at any zoom with far fewer vertices. Cubic curves are
+    /// approximated with quadratic ones first. This is the approach of Loop and Blinn.
+    ///
+    /// Curved edges are anti-aliased in the fragment shader, straight edges still need MSAA or
+    /// [`SvgAntiAliasing::Fringe`]. An SVG with [`AlphaMode::Opaque`] gets hard curved edges
+    /// instead. Strokes are still flattened, and curves whose triangles overlap each other, e.g.
+    /// in narrow and strongly bent shapes, may show artifacts. The coverage is computed in the
+    /// fragment shader, which is only done for the [`Svg`] material.
+    Analytic,
+}
+
+/// Which vertex attributes the mesh of an [`Svg`] gets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Default, Debug)]
+pub enum SvgVertexAttributes {
+    /// Only positions and colors, and the attributes of strokes that keep their width on screen,
+    /// of the anti-aliasing fringe and of analytic curves, which is all the [`Svg`] material
+    /// renders with.
+    #[default]
+    Svg,
+    /// Normals, tangents and UVs, mapped across the size of the SVG, as well, which a
+    /// `StandardMaterial` needs to light the SVG, e.g. with an `Svg3dStandardMaterial`. They
+    /// make every vertex 36 bytes larger.
+    Standard,
+}
+
+/// The key of an [`Svg`] material used to specialize its render pipeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SvgMaterialKey {
+    /// The visible sides of the SVG.
+    pub sides: SvgSides,
+    /// How the edges are anti-aliased.
+    pub anti_aliasing: SvgAntiAliasing,
+    /// How the curves of the fills are rendered.
+    pub curves: SvgCurves,
+}
+
+impl From<&Svg> for SvgMaterialKey {
+    fn from(svg: &Svg) -> Self {
+        Self {
+            sides: svg.sides,
+            anti_aliasing: svg.anti_aliasing,
+            curves: svg.curves,
+        }
+    }
+}
+
+pub(crate) use uniform::SvgMaterialUniform;
+
+mod uniform {
+    // the field checks generated by `ShaderType` are never called
+    #![allow(dead_code)]
+
+    use bevy::render::render_resource::ShaderType;
+
+    /// The GPU representation of the uniform data of an [`Svg`](super::Svg) material.
+    #[derive(Clone, Default, ShaderType)]
+    pub(crate) struct SvgMaterialUniform {
+        /// The alpha mode of the SVG, see `svg_material.wgsl` for the values.
+        pub(crate) alpha_mode: u32,
+        /// The cutoff for masked alpha.
+        pub(crate) alpha_cutoff: f32,
+        /// The x-coordinate of the center of the content of the SVG, around which its back is
+        /// mirrored with [`SvgSides::MirroredBack`](super::SvgSides::MirroredBack).
+        pub(crate) mirror_x: f32,
+    }
+}
+
+impl AsBindGroupShaderType<SvgMaterialUniform> for Svg {
+    fn as_bind_group_shader_type(&self, _images: &RenderAssets<GpuImage>) -> SvgMaterialUniform {
+        let (alpha_mode, alpha_cutoff) = match self.render_alpha_mode() {
+            AlphaMode::Opaque => (0, 0.5),
+            AlphaMode::Mask(cutoff) => (1, cutoff),
+            AlphaMode::Blend => (2, 0.5),
+            AlphaMode::Premultiplied => (3, 0.5),
+            AlphaMode::AlphaToCoverage => (4, 0.5),
+            AlphaMode::Add => (5, 0.5),
+            AlphaMode::Multiply => (6, 0.5),
+        };
+        SvgMaterialUniform {
+            alpha_mode,
+            alpha_cutoff,
+            // The back covers the same area as the front, wherever the origin of the SVG is
+            mirror_x: if self.content_bounds.is_empty() {
+                self.size.x / 2.0
+            } else {
+                self.content_bounds.center().x
+            },
+        }
+    }
 }
 
 impl Default for Svg {
@@ -50,54 +287,291 @@ impl Default for Svg {
                 w: 0.,
                 h: 0.,
             },
+            preserve_aspect_ratio: Default::default(),
+            content_bounds: Default::default(),
             paths: Default::default(),
             mesh: Default::default(),
+            alpha_mode: Default::default(),
+            sides: Default::default(),
+            depth_offset: Default::default(),
+            stroke_scaling: Default::default(),
+            anti_aliasing: Default::default(),
+            curves: Default::default(),
+            vertex_attributes: Default::default(),
+            #[cfg(feature = "rasterize")]
+            tree: None,
+            tessellated_from: None,
         }
     }
 }
 
+impl FromStr for Svg {
+    type Err = FileSvgError;
+
+    /// Parses an SVG from a string, without tessellating it, see [`Svg::from_str_with_mesh`].
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text.as_bytes(), PathBuf::from("<string>"), default_fonts())
+    }
+}
+
+/// Loads the system fonts and the fonts in `font_dir`.
+fn load_fonts(font_dir: PathBuf) -> usvg::fontdb::Database {
+    let mut fontdb = usvg::fontdb::Database::default();
+    fontdb.load_system_fonts();
+    debug!("loading fonts in {:?}", font_dir);
+    fontdb.load_fonts_dir(font_dir);
+    fontdb
+}
+
+/// The system fonts and the fonts in `./assets`, loaded once and shared by all SVGs.
+pub(crate) fn default_fonts() -> Arc<usvg::fontdb::Database> {
+    static FONTS: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| Arc::new(load_fonts("./assets".into())))
+        .clone()
+}
+
 impl Svg {
     /// Loads an SVG from bytes
+    ///
+    /// The `path` is only used in errors. Text is rendered with the system fonts and the fonts
+    /// in `fonts`, or in `./assets` if it is `None`.
     pub fn from_bytes(
         bytes: &[u8],
         path: impl Into<PathBuf> + Copy,
         fonts: Option<impl Into<PathBuf>>,
     ) -> Result<Svg, FileSvgError> {
-        let mut fontdb = usvg::fontdb::Database::default();
-        fontdb.load_system_fonts();
-        let font_dir = fonts.map_or("./assets".into(), Into::into);
-        debug!("loading fonts in {:?}", font_dir);
-        fontdb.load_fonts_dir(font_dir);
+        let fontdb = match fonts {
+            Some(font_dir) => Arc::new(load_fonts(font_dir.into())),
+            None => default_fonts(),
+        };
+        Self::parse(bytes, path.into(), fontdb)
+    }
+
+    /// Parses an SVG from a string and tessellates it into a mesh that is added to `meshes`, so
+    /// it is ready to be added to `Assets<Svg>` and spawned.
+    ///
+    /// Text is rendered with the system fonts and the fonts in `./assets`, which are only loaded
+    /// once for all SVGs. To parse an SVG without a mesh, e.g. to tessellate it later, use
+    /// [`str::parse`].
+    ///
+    /// ```ignore
+    /// fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>, mut svgs: ResMut<Assets<Svg>>) {
+    ///     let svg = Svg::from_str_with_mesh(SVG_TEXT, &mut meshes).unwrap();
+    ///     commands.spawn(Svg2d(svgs.add(svg)));
+    /// }
+    /// ```
+    pub fn from_str_with_mesh(text: &str, meshes: &mut Assets<Mesh>) -> Result<Svg, FileSvgError> {
+        let mut svg: Svg = text.parse()?;
+        svg.mesh = meshes.add(svg.tessellate());
+        svg.tessellated_from = Some(svg.tessellation_hash());
+        Ok(svg)
+    }
+
+    fn parse(
+        bytes: &[u8],
+        path: PathBuf,
+        fontdb: Arc<usvg::fontdb::Database>,
+    ) -> Result<Svg, FileSvgError> {
+        let map_err = |err: usvg::Error| FileSvgError {
+            error: err.into(),
+            path: format!("{}", path.display()),
+        };
 
-        let fontdb = Arc::new(fontdb);
+        let data = if bytes.starts_with(&[0x1f, 0x8b]) {
+            Cow::Owned(usvg::decompress_svgz(bytes).map_err(map_err)?)
+        } else {
+            Cow::Borrowed(bytes)
+        };
+        let text = std::str::from_utf8(&data).map_err(|_| map_err(usvg::Error::NotAnUtf8Str))?;
+        let parse = |text| {
+            usvg::roxmltree::Document::parse_with_options(
+                text,
+                usvg::roxmltree::ParsingOptions {
+                    allow_dtd: true,
+                    ..Default::default()
+                },
+            )
+            .map_err(|err| map_err(usvg::Error::ParsingFailed(err)))
+        };
+        let xml_tree = parse(text)?;
+        // usvg ignores `vector-effect`, so the elements need to be found before it drops them
+        let non_scaling_strokes = non_scaling_strokes(&xml_tree, &path);
 
-        let svg_tree = usvg::Tree::from_data(
-            bytes,
+        let svg_tree = usvg::Tree::from_xmltree(
+            &xml_tree,
             &usvg::Options {
                 fontdb,
                 ..Default::default()
             },
         )
-        .map_err(|err| FileSvgError {
-            error: err.into(),
-            path: format!("{}", path.into().display()),
-        })?;
+        .map_err(map_err)?;
+
+        let mut svg = Svg::from_tree(svg_tree, &non_scaling_strokes);
+        for path in Arc::make_mut(&mut svg.paths) {
+            if path.non_scaling_stroke {
+                path.bake_transform();
+            }
+        }
+
+        // usvg bakes the `viewBox` into the root transform, so we need to read it ourself
+        let root = xml_tree.root_element();
+        if let Some(view_box) = root
+            .attribute("viewBox")
+            .and_then(|attr| attr.parse::<ViewBox>().ok())
+        {
+            svg.view_box = view_box;
+        }
+        if let Some(aspect) = root
+            .attribute("preserveAspectRatio")
+            .and_then(|attr| attr.parse::<AspectRatio>().ok())
+        {
+            svg.preserve_aspect_ratio = aspect;
+        }
+
+        Ok(svg)
+    }
 
-        Ok(Svg::from_tree(svg_tree))
+    /// Returns the rectangle that should be used as reference, e.g. for an [`Origin`](crate::prelude::Origin),
+    /// in SVG coordinates, i.e. the y-axis pointing down.
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub fn bounds(&self, bounds: crate::origin::OriginBounds) -> Rect {
+        use crate::origin::OriginBounds;
+
+        match bounds {
+            OriginBounds::Size => Rect::from_corners(Vec2::ZERO, self.size),
+            OriginBounds::ViewBox => {
+                let view_box_size = Vec2::new(self.view_box.w as f32, self.view_box.h as f32);
+                if view_box_size.x <= 0.0 || view_box_size.y <= 0.0 {
+                    return Rect::from_corners(Vec2::ZERO, self.size);
+                }
+                // The viewBox is mapped onto the document size, the same way usvg does it.
+                let (scale, offset) =
+                    crate::util::aspect::fit(view_box_size, self.size, self.preserve_aspect_ratio);
+                Rect::from_corners(offset, offset + view_box_size * scale)
+            }
+            OriginBounds::Content => self.content_bounds,
+        }
     }
 
     /// Creates a bevy mesh from the SVG data.
+    ///
+    /// Besides positions and vertex colors, the mesh contains normals and tangents facing
+    /// the viewer and UVs which are mapped across the size of the SVG.
     pub fn tessellate(&self) -> Mesh {
-        let buffer = tessellation::generate_buffer(
-            self,
+        self.tessellate_with(
+            None,
             &mut FillTessellator::new(),
             &mut StrokeTessellator::new(),
-        );
-        buffer.convert()
+        )
+    }
+
+    /// Creates a bevy mesh from the SVG data, like [`Svg::tessellate`], but with the given
+    /// tolerance for all fills and strokes instead of their own.
+    ///
+    /// The tolerance is the maximum distance, in SVG units, between the tessellated and the
+    /// real curves. Smaller tolerances give smoother curves, at the cost of more triangles.
+    pub fn tessellate_with_tolerance(&self, tolerance: f32) -> Mesh {
+        self.tessellate_with(
+            Some(tolerance),
+            &mut FillTessellator::new(),
+            &mut StrokeTessellator::new(),
+        )
+    }
+
+    pub(crate) fn tessellate_with(
+        &self,
+        tolerance: Option<f32>,
+        fill_tess: &mut FillTessellator,
+        stroke_tess: &mut StrokeTessellator,
+    ) -> Mesh {
+        let buffer = tessellation::generate_buffer(self, tolerance, fill_tess, stroke_tess);
+        (
+            buffer,
+            Rect::from_corners(Vec2::ZERO, self.size),
+            self.vertex_attributes,
+        )
+            .convert()
+    }
+
+    /// Hashes everything the mesh of the SVG is tessellated from.
+    pub(crate) fn tessellation_hash(&self) -> TessellationHash {
+        let mut hasher = DefaultHasher::new();
+        let hasher = &mut hasher;
+        hash_f32s(hasher, [self.size.x, self.size.y, self.depth_offset]);
+        self.stroke_scaling.hash(hasher);
+        self.anti_aliasing.hash(hasher);
+        self.curves.hash(hasher);
+        self.vertex_attributes.hash(hasher);
+        let settings = hasher.finish();
+
+        let mut hasher = DefaultHasher::new();
+        let hasher = &mut hasher;
+        self.paths.len().hash(hasher);
+        for path in self.paths.iter() {
+            hash_f32s(hasher, path.color.to_linear().to_f32_array());
+            let t = path.abs_transform;
+            hash_f32s(hasher, [t.sx, t.kx, t.ky, t.sy, t.tx, t.ty]);
+            path.is_stroke.hash(hasher);
+            path.non_scaling_stroke.hash(hasher);
+            match path.draw_type {
+                DrawType::Fill => 0_u8.hash(hasher),
+                DrawType::Stroke(options) => {
+                    1_u8.hash(hasher);
+                    (options.start_cap as u8).hash(hasher);
+                    (options.end_cap as u8).hash(hasher);
+                    (options.line_join as u8).hash(hasher);
+                    hash_f32s(
+                        hasher,
+                        [options.line_width, options.miter_limit, options.tolerance],
+                    );
+                }
+            }
+            path.segments.len().hash(hasher);
+            for segment in &path.segments {
+                match *segment {
+                    PathEvent::Begin { at } => {
+                        0_u8.hash(hasher);
+                        hash_f32s(hasher, [at.x, at.y]);
+                    }
+                    PathEvent::Line { to, .. } => {
+                        1_u8.hash(hasher);
+                        hash_f32s(hasher, [to.x, to.y]);
+                    }
+                    PathEvent::Quadratic { ctrl, to, .. } => {
+                        2_u8.hash(hasher);
+                        hash_f32s(hasher, [ctrl.x, ctrl.y, to.x, to.y]);
+                    }
+                    PathEvent::Cubic {
+                        ctrl1, ctrl2, to, ..
+                    } => {
+                        3_u8.hash(hasher);
+                        hash_f32s(hasher, [ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y]);
+                    }
+                    PathEvent::End { close, .. } => {
+                        4_u8.hash(hasher);
+                        close.hash(hasher);
+                    }
+                }
+            }
+        }
+
+        TessellationHash {
+            paths: hasher.finish(),
+            settings,
+        }
     }
 
-    pub(crate) fn from_tree(tree: usvg::Tree) -> Svg {
-        let view_box = tree.root().layer_bounding_box();
+    /// The alpha mode the SVG is rendered with, which blends opaque SVGs with an anti-aliasing fringe.
+    pub(crate) fn render_alpha_mode(&self) -> AlphaMode {
+        match (self.alpha_mode, self.anti_aliasing) {
+            (AlphaMode::Opaque, SvgAntiAliasing::Fringe) => AlphaMode::Blend,
+            (alpha_mode, _) => alpha_mode,
+        }
+    }
+
+    fn from_tree(tree: usvg::Tree, non_scaling_strokes: &NonScalingStrokes) -> Svg {
+        let content_bounds = tree.root().layer_bounding_box();
         let size = tree.size();
         let mut descriptors = Vec::new();
 
@@ -106,6 +580,7 @@ impl Svg {
             node: &'a usvg::Node,
             transform: usvg::Transform,
             is_text: bool,
+            non_scaling_stroke: bool,
         }
 
         let mut node_stack = tree
@@ -119,6 +594,7 @@ impl Svg {
                 node,
                 transform: node.abs_transform(),
                 is_text: false,
+                non_scaling_stroke: false,
             })
             .collect::<VecDeque<_>>();
 
@@ -126,6 +602,7 @@ impl Svg {
             node,
             transform,
             is_text,
+            non_scaling_stroke,
         }) = node_stack.pop_front()
         {
             trace!("---");
@@ -134,12 +611,15 @@ impl Svg {
                 usvg::Node::Group(ref group) => {
                     let transform = transform.pre_concat(group.transform());
                     trace!("group: {:?}", group.id());
+                    let non_scaling_stroke =
+                        non_scaling_stroke || non_scaling_strokes.uses.contains(group.id());
                     if !group.should_isolate() {
                         for node in group.children() {
                             node_stack.push_front(NodeContext {
                                 node,
                                 transform,
                                 is_text: false,
+                                non_scaling_stroke,
                             });
                         }
                     } else {
@@ -158,6 +638,7 @@ impl Svg {
                             node,
                             transform,
                             is_text: true,
+                            non_scaling_stroke,
                         });
                     }
                 }
@@ -180,15 +661,26 @@ impl Svg {
                         is_stroke: false,
                     };
 
+                    let non_scaling_stroke =
+                        non_scaling_stroke || non_scaling_strokes.elements.contains(path.id());
+
                     // inverted because we are reversing the list at the end
                     match path.paint_order() {
                         PaintOrder::FillAndStroke => {
-                            Self::process_stroke(&mut descriptors, path_with_transform);
+                            Self::process_stroke(
+                                &mut descriptors,
+                                path_with_transform,
+                                non_scaling_stroke,
+                            );
                             Self::process_fill(&mut descriptors, path_with_transform);
                         }
                         PaintOrder::StrokeAndFill => {
                             Self::process_fill(&mut descriptors, path_with_transform);
-                            Self::process_stroke(&mut descriptors, path_with_transform);
+                            Self::process_stroke(
+                                &mut descriptors,
+                                path_with_transform,
+                                non_scaling_stroke,
+                            );
                         }
                     }
                 }
@@ -200,17 +692,40 @@ impl Svg {
 
         descriptors.reverse();
 
+        let alpha_mode = if descriptors.iter().any(|path| path.color.alpha() < 1.0) {
+            AlphaMode::Blend
+        } else {
+            AlphaMode::Opaque
+        };
+
         Svg {
             name: Default::default(),
             size: Vec2::new(size.width(), size.height()),
             view_box: ViewBox {
-                x: view_box.x() as f64,
-                y: view_box.y() as f64,
-                w: view_box.width() as f64,
-                h: view_box.height() as f64,
+                x: 0.0,
+                y: 0.0,
+                w: size.width() as f64,
+                h: size.height() as f64,
             },
-            paths: descriptors,
+            preserve_aspect_ratio: AspectRatio::default(),
+            content_bounds: Rect::new(
+                content_bounds.left(),
+                content_bounds.top(),
+                content_bounds.right(),
+                content_bounds.bottom(),
+            ),
+            paths: Arc::new(descriptors),
             mesh: Default::default(),
+            alpha_mode,
+            sides: Default::default(),
+            depth_offset: Default::default(),
+            stroke_scaling: Default::default(),
+            anti_aliasing: Default::default(),
+            curves: Default::default(),
+            vertex_attributes: Default::default(),
+            #[cfg(feature = "rasterize")]
+            tree: Some(Arc::new(tree)),
+            tessellated_from: None,
         }
     }
 
@@ -242,17 +757,20 @@ impl Svg {
         };
 
         descriptors.alloc().init(PathDescriptor {
+            id: path.id().to_string(),
             abs_transform: path_with_transform.transform,
             segments: path_with_transform.convert().collect(),
             color,
             draw_type: DrawType::Fill,
             is_stroke: false,
+            non_scaling_stroke: false,
         });
     }
 
     fn process_stroke(
         descriptors: &mut Vec<PathDescriptor>,
         path_with_transform: PathWithTransform,
+        non_scaling_stroke: bool,
     ) {
         let mut path_with_transform = path_with_transform;
         let path = path_with_transform.path;
@@ -262,22 +780,130 @@ impl Svg {
         path_with_transform.is_stroke = true;
 
         descriptors.alloc().init(PathDescriptor {
+            id: path.id().to_string(),
             segments: path_with_transform.convert().collect(),
             abs_transform: path_with_transform.transform,
             color,
             draw_type,
             is_stroke: true,
+            non_scaling_stroke,
         });
     }
 }
 
+fn hash_f32s<const N: usize>(hasher: &mut DefaultHasher, values: [f32; N]) {
+    for value in values {
+        value.to_bits().hash(hasher);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PathDescriptor {
+    pub id: String,
     pub segments: Vec<PathEvent>,
     pub color: Color,
     pub draw_type: DrawType,
     pub abs_transform: usvg::Transform,
     pub is_stroke: bool,
+    /// Whether the stroke keeps its width on screen, regardless of [`Svg::stroke_scaling`], as
+    /// with `vector-effect="non-scaling-stroke"`.
+    pub non_scaling_stroke: bool,
+}
+
+impl PathDescriptor {
+    /// Applies the transform to the segments, so that a stroke isn't scaled by it.
+    fn bake_transform(&mut self) {
+        let t = self.abs_transform;
+        let transform = Transform::new(t.sx, t.ky, t.kx, t.sy, t.tx, t.ty);
+        for segment in &mut self.segments {
+            *segment = segment.transformed(&transform);
+        }
+        self.abs_transform = usvg::Transform::identity();
+    }
+}
+
+/// Whether the element has `vector-effect="non-scaling-stroke"`, either as attribute or as style.
+fn is_non_scaling_stroke(node: &usvg::roxmltree::Node<'_, '_>) -> bool {
+    let is_non_scaling = |value: &str| value.trim() == "non-scaling-stroke";
+    node.attribute("vector-effect").is_some_and(is_non_scaling)
+        || node.attribute("style").is_some_and(|style| {
+            style.split(';').any(|declaration| {
+                declaration.split_once(':').is_some_and(|(name, value)| {
+                    name.trim() == "vector-effect" && is_non_scaling(value)
+                })
+            })
+        })
+}
+
+/// The elements with a non-scaling stroke, by id, since usvg drops `vector-effect`.
+#[derive(Default)]
+struct NonScalingStrokes {
+    /// Ids of the elements with a non-scaling stroke, which usvg keeps on their paths.
+    elements: HashSet<String>,
+    /// Ids of the `<use>` elements that draw one of them. usvg clears the ids of the content a
+    /// `<use>` draws, but keeps the id of the `<use>` on the group it converts it to.
+    uses: HashSet<String>,
+}
+
+/// How many `<use>` elements linking to each other are followed to find a non-scaling stroke.
+const MAX_USE_DEPTH: usize = 16;
+
+/// Finds the elements with a non-scaling stroke in the document, before usvg converts it. Elements
+/// without an id can't be found among the converted paths, so their strokes are scaled.
+fn non_scaling_strokes<'a, 'input>(
+    xml_tree: &'a usvg::roxmltree::Document<'input>,
+    path: &std::path::Path,
+) -> NonScalingStrokes {
+    const XLINK_NS: &str = "http://www.w3.org/1999/xlink";
+
+    let elements_by_id: HashMap<&str, usvg::roxmltree::Node<'a, 'input>> = xml_tree
+        .descendants()
+        .filter_map(|node| node.attribute("id").map(|id| (id, node)))
+        .collect();
+    let is_use = |node: &usvg::roxmltree::Node<'_, '_>| node.tag_name().name() == "use";
+    // A `<use>` draws the element it links to, which can be another `<use>`
+    let draws_non_scaling_stroke = |mut node: usvg::roxmltree::Node<'a, 'input>| {
+        for _ in 0..MAX_USE_DEPTH {
+            if is_non_scaling_stroke(&node) {
+                return true;
+            }
+            let Some(target) = is_use(&node)
+                .then(|| {
+                    node.attribute((XLINK_NS, "href"))
+                        .or(node.attribute("href"))
+                })
+                .flatten()
+                .and_then(|href| elements_by_id.get(href.trim().strip_prefix('#')?))
+            else {
+                return false;
+            };
+            node = *target;
+        }
+        false
+    };
+
+    let mut strokes = NonScalingStrokes::default();
+    for node in xml_tree
+        .descendants()
+        .filter(|node| node.is_element() && draws_non_scaling_stroke(*node))
+    {
+        let ids = if is_use(&node) {
+            &mut strokes.uses
+        } else {
+            &mut strokes.elements
+        };
+        match node.attribute("id").filter(|id| !id.is_empty()) {
+            Some(id) => {
+                ids.insert(id.to_string());
+            }
+            None => warn!(
+                "`<{}>` in SVG `{}` draws a non-scaling stroke, but has no id to find it by, so its stroke is scaled.",
+                node.tag_name().name(),
+                path.display()
+            ),
+        }
+    }
+    strokes
 }
 
 #[derive(Debug, Clone)]
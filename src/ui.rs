@@ -0,0 +1,168 @@
+//! SVGs in `bevy_ui` layouts.
+
+use bevy::{
+    asset::{AssetEvent, Assets, Handle},
+    ecs::{
+        change_detection::{DetectChanges, Ref},
+        component::Component,
+        event::EventReader,
+        query::With,
+        system::{Local, Query, Res, ResMut},
+    },
+    image::Image,
+    log::debug,
+    math::UVec2,
+    ui::{
+        prelude::{BorderRect, SliceScaleMode, TextureSlicer},
+        widget::{ImageMeasure, ImageNode, NodeImageMode},
+        ComputedNode, ContentSize, NodeMeasure, UiScale,
+    },
+    utils::HashSet,
+    window::{PrimaryWindow, Window},
+};
+
+use crate::{slice::SvgSlices, svg::Svg};
+
+/// Displays an [`Svg`] in a UI node.
+///
+/// The SVG is rasterized at the computed size of the node, in physical pixels, and fitted into
+/// it following its `preserveAspectRatio`. It is shown through the [`ImageNode`] of the entity,
+/// so it is clipped and ordered like any other UI image, and tinted by the color of the
+/// [`ImageNode`]. A node without a size of its own takes the size of the SVG.
+///
+/// The SVG is rasterized again whenever the size of the node changes, which is costly for
+/// large or complex SVGs that are resized every frame. An `SvgNode` with [`SvgSlices`] is
+/// instead rasterized once at the size of the SVG, and nine-sliced onto the node.
+#[derive(Clone, Component, Debug, Default)]
+#[require(ImageNode(svg_image_node), SvgNodeImage)]
+pub struct SvgNode(pub Handle<Svg>);
+
+/// The image node of an [`SvgNode`], which is stretched over the node so it isn't measured
+/// by its image.
+fn svg_image_node() -> ImageNode {
+    ImageNode {
+        image_mode: NodeImageMode::Stretch,
+        ..Default::default()
+    }
+}
+
+/// The size in physical pixels the [`SvgNode`] was last rasterized at.
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SvgNodeImage {
+    size: UVec2,
+    sliced: bool,
+}
+
+/// Gives every [`SvgNode`] the size of its SVG as content size, so nodes without a size of their
+/// own are laid out at the size of the SVG.
+///
+/// An [`ImageNode`] that isn't measured by its image removes the content size whenever it
+/// changes, so this needs to run after `update_image_content_size_system`.
+pub fn update_svg_node_content_size(
+    mut previous_scale_factor: Local<f32>,
+    mut svg_events: EventReader<AssetEvent<Svg>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    svgs: Res<Assets<Svg>>,
+    mut query: Query<(Ref<SvgNode>, Ref<ImageNode>, &mut ContentSize)>,
+) {
+    let scale_factor = windows
+        .get_single()
+        .map_or(1.0, |window| window.resolution.scale_factor())
+        * ui_scale.0;
+    let loaded = svg_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    for (svg_node, image_node, mut content_size) in &mut query {
+        if !(svg_node.is_changed()
+            || image_node.is_changed()
+            || content_size.is_added()
+            || loaded.contains(&svg_node.0.id())
+            || scale_factor != *previous_scale_factor)
+        {
+            continue;
+        }
+        let Some(svg) = svgs.get(&svg_node.0) else {
+            continue;
+        };
+        content_size.set(NodeMeasure::Image(ImageMeasure {
+            size: svg.size * scale_factor,
+        }));
+    }
+
+    *previous_scale_factor = scale_factor;
+}
+
+/// Rasterizes every [`SvgNode`] whose node was resized, or whose SVG or [`SvgSlices`] changed,
+/// into a new image.
+pub fn rasterize_svg_nodes(
+    mut svg_events: EventReader<AssetEvent<Svg>>,
+    svgs: Res<Assets<Svg>>,
+    mut images: ResMut<Assets<Image>>,
+    mut query: Query<(
+        Ref<SvgNode>,
+        &ComputedNode,
+        Option<Ref<SvgSlices>>,
+        &mut ImageNode,
+        &mut SvgNodeImage,
+    )>,
+) {
+    let loaded = svg_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    for (svg_node, computed_node, slices, mut image_node, mut node_image) in &mut query {
+        let Some(svg) = svgs.get(&svg_node.0) else {
+            continue;
+        };
+        let scale_factor = computed_node.inverse_scale_factor().recip();
+        // Sliced nodes stretch an image of the SVG at its own size
+        let size = if slices.is_some() {
+            (svg.size * scale_factor).round().as_uvec2()
+        } else {
+            computed_node.size().round().as_uvec2()
+        };
+        if size.x == 0 || size.y == 0 {
+            continue;
+        }
+        if size == node_image.size
+            && slices.is_some() == node_image.sliced
+            && !slices.as_ref().is_some_and(DetectChanges::is_changed)
+            && !svg_node.is_changed()
+            && !loaded.contains(&svg_node.0.id())
+        {
+            continue;
+        }
+        debug!("Svg node of `{}` resized to {size}.", svg.name);
+        let image = if let Some(slices) = &slices {
+            image_node.image_mode = NodeImageMode::Sliced(TextureSlicer {
+                border: BorderRect {
+                    left: slices.left * scale_factor,
+                    right: slices.right * scale_factor,
+                    top: slices.top * scale_factor,
+                    bottom: slices.bottom * scale_factor,
+                },
+                center_scale_mode: SliceScaleMode::Stretch,
+                sides_scale_mode: SliceScaleMode::Stretch,
+                max_corner_scale: 1.0,
+            });
+            svg.rasterize(size.x, size.y)
+        } else {
+            image_node.image_mode = NodeImageMode::Stretch;
+            svg.rasterize_with_aspect_ratio(size.x, size.y, svg.preserve_aspect_ratio)
+        };
+        // The previous image is dropped together with its handle
+        image_node.image = images.add(image);
+        node_image.size = size;
+        node_image.sliced = slices.is_some();
+    }
+}
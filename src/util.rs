@@ -30,3 +30,60 @@ pub mod paint {
         Color::Srgba(Srgba::from_f32_array(avg))
     }
 }
+
+pub mod aspect {
+    use bevy::math::Vec2;
+    use svgtypes::{Align, AspectRatio};
+
+    /// Fits `content` into `target` following the rules of `preserveAspectRatio`.
+    ///
+    /// Returns the scale that has to be applied to the content and the offset of the
+    /// scaled content inside of `target`, both with the y-axis pointing down.
+    pub fn fit(content: Vec2, target: Vec2, aspect: AspectRatio) -> (Vec2, Vec2) {
+        let scale = target / content;
+        let scale = match aspect.align {
+            Align::None => scale,
+            _ if aspect.slice => Vec2::splat(scale.max_element()),
+            _ => Vec2::splat(scale.min_element()),
+        };
+
+        let free = target - content * scale;
+        let factor = match aspect.align {
+            Align::None | Align::XMinYMin => Vec2::new(0.0, 0.0),
+            Align::XMidYMin => Vec2::new(0.5, 0.0),
+            Align::XMaxYMin => Vec2::new(1.0, 0.0),
+            Align::XMinYMid => Vec2::new(0.0, 0.5),
+            Align::XMidYMid => Vec2::new(0.5, 0.5),
+            Align::XMaxYMid => Vec2::new(1.0, 0.5),
+            Align::XMinYMax => Vec2::new(0.0, 1.0),
+            Align::XMidYMax => Vec2::new(0.5, 1.0),
+            Align::XMaxYMax => Vec2::new(1.0, 1.0),
+        };
+
+        (scale, free * factor)
+    }
+}
+
+pub mod geometry {
+    use bevy::math::Vec2;
+
+    /// Checks if a point lies within the given contours, following the even-odd fill rule.
+    pub fn is_inside(point: Vec2, contours: &[Vec<Vec2>]) -> bool {
+        let mut inside = false;
+        for contour in contours {
+            let Some(&last) = contour.last() else {
+                continue;
+            };
+            let mut prev = last;
+            for &curr in contour {
+                if (curr.y > point.y) != (prev.y > point.y)
+                    && point.x < (prev.x - curr.x) * (point.y - curr.y) / (prev.y - curr.y) + curr.x
+                {
+                    inside = !inside;
+                }
+                prev = curr;
+            }
+        }
+        inside
+    }
+}